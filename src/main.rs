@@ -1,6 +1,22 @@
+use accesskit::{Live, Node as AccessNode, Role};
 use bevy::{
+    a11y::AccessibilityNode,
+    asset::{
+        AssetLoader,
+        LoadContext,
+        io::Reader,
+    },
+    audio::{
+        AddAudioSource,
+        Decodable,
+        Sample,
+        SpatialScale,
+        Source,
+    },
     ecs::relationship::RelatedSpawnerCommands,
+    input_focus::InputFocus,
     prelude::*,
+    sprite::Anchor,
     window::{
         PrimaryWindow,
         WindowResized,
@@ -8,12 +24,19 @@ use bevy::{
     }
 };
 use rand::prelude::*;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::{
+    Arc,
+    atomic::{AtomicU32, AtomicU8, Ordering},
+};
+use std::time::Duration;
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::{SystemTime, UNIX_EPOCH};
+#[cfg(target_arch = "wasm32")]
+use web_time::{SystemTime, UNIX_EPOCH};
 
-const TAG_HAT_1: i8 = 0;
-const TAG_HAT_14: i8 = 13;
-const TAG_BODY_1: i8 = 14;
-const TAG_BODY_8: i8 = 21;
+type GhostTag = u16;
 
 const GAME_AREA_WIDTH: f32 = 3840.0;
 const GAME_AREA_HEIGHT: f32 = 2399.0;
@@ -27,47 +50,98 @@ const LANE_LAYOUT_LANE_COUNT: u8 = 9;
 const LANE_LAYOUT_MARGIN: f32 = 100.0;
 const LANE_LAYOUT_BUFFER_LANES: u8 = 2;
 const LANE_LAYOUT_SPAWN_LANES: u8 = LANE_LAYOUT_LANE_COUNT - LANE_LAYOUT_BUFFER_LANES - LANE_LAYOUT_BUFFER_LANES;
+const LANE_LAYOUT_CENTER_LANE: u8 = 4;
 const LANE_LAYOUT_DESPAWN_LEFT: f32 = -2000.0;
 const LANE_LAYOUT_DESPAWN_RIGHT: f32 = 2000.0;
 
 const Z_POS_BACKGROUND: f32 = -10.0;
+const Z_POS_LANE_OVERLAY: f32 = -9.0;
 const Z_POS_GHOSTS: f32 = -8.0;
 const Z_POS_FRAME: f32 = -2.0;
 const Z_POS_DEVICE_BACK: f32 = -1.0;
 
 const GHOST_SPRITE_SCALE: f32 = 0.4;
+const GHOST_BEHAVIOUR_CHANCE: f64 = 0.3;
+// how many remotes get rolled for a set of targets before picking new targets
+const GHOST_WAVE_MAX_ATTEMPTS: usize = 200;
+const LANE_HAZARD_CHANCE: f64 = 0.35;
+const CAPTURE_ZONE_SPLIT_CHANCE: f64 = 0.3;
+// every target needs two bodies of its own for lookalikes and the first needs one more, so the
+// catalog's eight bodies run out past this
+const CAPTURE_ZONE_MAX_TARGETS: usize = 3;
+
+const CAPTURE_ZONE_COLORS: [Color; 2] = [
+    Color::srgb(1.0, 0.8, 0.2),
+    Color::srgb(0.3, 0.9, 1.0),
+];
+// the first one lines up with the capture button drawn on the frame
+const CAPTURE_BUTTON_POSITIONS: [Vec2; 2] = [
+    Vec2::new(-345.0, 1075.0),
+    Vec2::new(750.0, 1075.0),
+];
 const GHOST_SHADOW_SCALE: f32 = 0.7;
+const Z_POS_LEGEND: f32 = 5.0;
+const Z_POS_NOTE_PICKER: f32 = 6.0;
+const NOTE_MARKS_PER_BUTTON: usize = 5;
+const WAVE_BADGE_HEIGHT: f32 = 1150.0;
+const MOVE_LABEL_FADE_SECONDS: f32 = 1.5;
+const WAVE_SYNTH_SAMPLE_RATE: u32 = 44100;
+// a C major 9th, one note per button, so any mix of waves still sounds like it belongs
+const WAVE_SYNTH_BASE_FREQUENCIES: [f32; 5] = [130.81, 164.81, 196.0, 246.94, 293.66];
+const WAVE_SYNTH_FADE_SECONDS: f32 = 0.05;
+const HUM_PREVIEW_SECONDS: f32 = 3.0;
+const HUM_PREVIEW_VOLUME: f32 = 0.15;
+const COUNTER_DIGIT_WIDTH: f32 = 100.0;
+const COUNTER_TICK_SECONDS: f32 = 0.08;
+const COUNTER_DIGIT_COLOR: Color = Color::srgb(0.47, 0.05, 0.33);
+const REDUCED_MOTION_BOB_SCALE: f32 = 0.25;
+const REDUCED_MOTION_EMIT_PERIOD: f32 = 0.25;
+const REDUCED_MOTION_FADE_SECONDS: f32 = 0.8;
+const REDUCED_MOTION_FADE_ALPHA: f32 = 0.15;
+const SFX_MAX_VOICES: usize = 8;
+const SFX_MAX_VOICES_PER_SOUND: usize = 3;
+const SFX_PITCH_VARIATION: f32 = 0.12;
+// how long it takes a music stem to fade all the way in or out
+const MUSIC_CROSSFADE_SECONDS: f32 = 1.5;
+const MUSIC_STING_NOTE_SECONDS: f32 = 0.16;
+const MUSIC_STING_WIN_NOTES: [f32; 4] = [523.25, 659.25, 783.99, 1046.5];
+const MUSIC_STING_LOSE_NOTES: [f32; 3] = [392.0, 311.13, 261.63];
+const Z_POS_MENU: f32 = 20.0;
+const TIMED_MODE_SECONDS: f32 = 90.0;
+const TIMED_MODE_WARNING_SECONDS: f32 = 10.0;
+const ENDLESS_FIRINGS_PER_WAVE: u8 = 3;
+const ENDLESS_WAVE_SIZE: usize = 3;
+const ENDLESS_MAX_GHOSTS: usize = 24;
+const ENDLESS_CHARGES_PER_TARGET: u8 = 2;
+const HIGH_SCORE_TABLE_SIZE: usize = 5;
+#[cfg(target_arch = "wasm32")]
+const NARRATION_ELEMENT_ID: &str = "graveyard-shifts-narration";
+const SAVE_FILE_PATH: &str = "graveyard_shifts_save.ron";
+#[cfg(not(target_arch = "wasm32"))]
+const DAILY_SUMMARY_PATH: &str = "graveyard_shifts_daily.txt";
+// days since the unix epoch, the first daily puzzle is 2025-11-01
+const DAILY_FIRST_DAY: u64 = 20393;
+const DAILY_ROUND_SALT: u64 = 0x0b00;
+const DAILY_FIELD_SALT: u64 = 0x0f1e1d;
+const DAILY_DRIFT_SALT: u64 = 0xd21f7;
+const DAILY_SUMMARY_ROW_LENGTH: usize = 5;
+const DAILY_SUMMARY_CELL_SIZE: f32 = 60.0;
+const DAILY_SUMMARY_CELL_SPACING: f32 = 72.0;
 
 const WINDOW_RESOLUTION_X: u32 = 960;
 const WINDOW_RESOLUTION_Y: u32 = 600; 
 
 
-const GHOST_BODY_NAMES: [&str; 8] = [
-    "Booloon",
-    "Ghoost",
-    "Ghostie",
-    "Handshee",
-    "Puppergeist",
-    "SoapSprite",
-    "Timboo",
-    "Yolkai",
-];
+// every loaded asset that isn't a ghost sprite or a string table, the counter panel meshes are
+// built in code so they never report a load state
+const LOADING_FIXED_ASSET_COUNT: usize = 25;
 
-const GHOST_HAT_NAMES: [&str; 14] = [
-    "arrow",
-    "belt",
-    "bow",
-    "cone",
-    "crown",
-    "flower",
-    "glasses",
-    "lollipop",
-    "mug",
-    "mustache",
-    "party",
-    "propellor",
-    "tophat",
-    "wings",
+// language code and string table for each translation, the first one fills in anything the
+// others are missing
+const LANGUAGES: [(&str, &str); 3] = [
+    ("en", "locale/en.strings.ron"),
+    ("fr", "locale/fr.strings.ron"),
+    ("es", "locale/es.strings.ron"),
 ];
 
 const GHOST_WAVE_NAMES: [&str; 5] = [
@@ -79,8 +153,11 @@ const GHOST_WAVE_NAMES: [&str; 5] = [
 ];
 
 fn main() {
-    let target_ghosts = choose_target_ghosts();
-    let ghost_wave = build_ghost_wave_config(&target_ghosts);
+    let ghost_catalog = GhostCatalog::load();
+    let mut rng = rand::rng();
+    let capture_zones = choose_capture_zones(&ghost_catalog, &mut rng);
+    let lane_layout = build_lane_layout(&capture_zones, &mut rng);
+    let (target_ghosts, ghost_wave) = choose_targets_and_wave(&ghost_catalog, &capture_zones, &lane_layout, &mut rng);
     App::new()
     .add_plugins(
         DefaultPlugins.set(
@@ -97,24 +174,53 @@ fn main() {
     .insert_state(GameState::default())
     .insert_resource(LoadingProgress{
         current: 0,
-        total: 157,
+        total: LOADING_FIXED_ASSET_COUNT + LANGUAGES.len() + ghost_catalog.ghost_count() + ghost_catalog.bodies.len(),
     })
-    .insert_resource(build_lane_layout())
+    .insert_resource(lane_layout)
     .insert_resource(Sprites::default())
     .insert_resource(AudioHandles::default())
+    .insert_resource(LocaleHandles::default())
+    .insert_resource(Strings::default())
+    .init_asset::<StringTable>()
+    .register_asset_loader(StringTableLoader)
+    .add_audio_source::<WaveSynth>()
+    .add_audio_source::<AdaptiveMusic>()
+    .add_audio_source::<SfxSynth>()
+    .insert_resource(SfxBank::default())
+    .insert_resource(MusicControls::default())
+    // accessibility updates only go out once something holds focus, even if it's nothing
+    .insert_resource(InputFocus::default())
+    .insert_resource(ghost_catalog)
+    .insert_resource(capture_zones)
     .insert_resource(target_ghosts)
     .insert_resource(ghost_wave)
     .insert_resource(UIEnabled { enabled: true, moving_ghosts: false, })
     //TODO: change this to change the difficulty
-    .insert_resource(PlayerResources { charges: 10, reputation: 5 })
+    .insert_resource(PlayerResources { charges: 10, reputation: 5, score: 0 })
+    .insert_resource(GameSettings { mode: GameMode::Classic })
+    .insert_resource(RoundTimer { remaining: TIMED_MODE_SECONDS })
+    .insert_resource(EndlessSpawner { firings_until_wave: ENDLESS_FIRINGS_PER_WAVE })
+    .insert_resource(SaveData::load())
+    .insert_resource(DailyChallenge::default())
+    .insert_resource(DriftRng(StdRng::from_rng(&mut rng)))
+    .insert_resource(DeductionNotebook::default())
+    .insert_resource(ButtonNotes::default())
+    .insert_resource(VersusTurn {
+        current: 0,
+        waiting: PlayerResources { charges: 10, reputation: 5, score: 0 },
+        last_charges: 10,
+    })
     .add_message::<CaptureGhostsInitialized>()
     .add_message::<RemoteFired>()
     .add_message::<GameWon>()
     .add_message::<GameLost>()
     .add_message::<GhostCaptured>()
+    .add_message::<PlaySfx>()
+    .add_message::<Narrate>()
     .add_systems(OnEnter(GameState::Loading), (
         load_sprites,
         load_audio,
+        load_localization,
         spawn_camera,
         spawn_loading_bar,
     ))
@@ -126,22 +232,55 @@ fn main() {
         despawn_loading_bar,
         spawn_ui,
         spawn_music,
+        spawn_narrator,
+        setup_localization,
+    ))
+    .add_systems(OnEnter(GameState::Menu), (
+        spawn_mode_menu,
+    ))
+    .add_systems(Update, (
+        handle_menu_clicks,
+    ).run_if(in_state(GameState::Menu)))
+    .add_systems(OnExit(GameState::Menu), (
+        despawn_mode_menu,
     ))
     .add_systems(OnEnter(GameState::Game), (
         spawn_ghosts,
+        spawn_lane_overlays,
+        spawn_capture_zones,
+        spawn_target_displays,
+        reset_round_timer,
+        reset_endless_spawner,
+        reset_versus_turn,
+        spawn_legend_panel,
+        narrate_round_start,
     ))
     .add_systems(Update, (
-        begin_scooting_ghosts,
+        // versus and the daily summary need each click resolved the frame it happens
+        begin_scooting_ghosts.after(handle_remote_clicks),
         handle_remote_clicks,
-        capture_ghosts,
+        capture_ghosts.after(handle_remote_clicks),
         handle_game_end,
         handle_ghosts_captured,
+        update_round_timer.after(begin_scooting_ghosts).after(capture_ghosts),
+        schedule_endless_waves,
+        track_daily_progress.after(begin_scooting_ghosts).after(capture_ghosts),
+        advance_versus_turn.after(begin_scooting_ghosts).after(capture_ghosts),
+        record_notebook_observations.after(begin_scooting_ghosts),
+        toggle_legend_panel,
+        update_hum_preview,
+        narrate_remote_changes,
     ).run_if(in_state(GameState::Game)))
     .add_systems(OnEnter(GameState::GameEnd), (
         spawn_reset_timer,
+        record_endless_score,
+        record_daily_result,
+        announce_versus_winner,
     ))
     .add_systems(Update, (
-        wait_for_reset
+        wait_for_reset,
+        #[cfg(target_arch = "wasm32")]
+        copy_daily_summary,
     ).run_if(in_state(GameState::GameEnd)))
     .add_systems(OnExit(GameState::GameEnd), (
         reset_game,
@@ -158,11 +297,32 @@ fn main() {
         update_remote_dial,
         update_wave_handle,
         update_counters,
+        update_target_ticks,
         update_ghost_soul_particles,
         update_burst_particle_roots,
         update_burst_particles,
         handle_ui_enabled,
     ))
+    .add_systems(Update, (
+        update_versus_turn_labels,
+        update_notebook_cells,
+        update_note_marks,
+        update_note_picker,
+        update_wave_badges,
+        spawn_move_labels,
+        fade_move_labels,
+        update_music_mix,
+        play_sfx,
+        toggle_accessibility,
+        spawn_state_indicators,
+        update_state_indicators,
+        update_ghost_palette,
+        fade_flash_effects,
+        narrate_round_end,
+        update_narrator,
+        toggle_language.run_if(not(in_state(GameState::Loading))),
+        update_localized_texts,
+    ))
     .run();
 }
 
@@ -170,17 +330,250 @@ fn main() {
 enum GameState {
     #[default]
     Loading,
+    Menu,
     Game,
     GameEnd,
 }
 
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum GameMode {
+    Classic,
+    Timed,
+    Endless,
+    Daily,
+    Versus,
+}
+impl GameMode {
+    const ALL: [GameMode; 5] = [
+        GameMode::Classic,
+        GameMode::Timed,
+        GameMode::Endless,
+        GameMode::Daily,
+        GameMode::Versus,
+    ];
+
+    fn label_key(&self) -> &'static str {
+        return match self {
+            GameMode::Classic => "mode.classic",
+            GameMode::Timed => "mode.timed",
+            GameMode::Endless => "mode.endless",
+            GameMode::Daily => "mode.daily",
+            GameMode::Versus => "mode.versus",
+        };
+    }
+
+    fn description_key(&self) -> &'static str {
+        return match self {
+            GameMode::Classic => "mode.classic.description",
+            GameMode::Timed => "mode.timed.description",
+            GameMode::Endless => "mode.endless.description",
+            GameMode::Daily => "mode.daily.description",
+            GameMode::Versus => "mode.versus.description",
+        };
+    }
+
+    fn keeps_score(&self) -> bool {
+        return matches!(self, GameMode::Timed | GameMode::Endless);
+    }
+}
+
+#[derive(Resource)]
+struct GameSettings {
+    mode: GameMode,
+}
+
+#[derive(Resource)]
+struct RoundTimer {
+    remaining: f32,
+}
+
+// In versus the active player's resources live in `PlayerResources` like any other mode, the
+// other player's wait here until it's their turn again
+#[derive(Resource)]
+struct VersusTurn {
+    current: usize,
+    waiting: PlayerResources,
+    // the active player's charges as of the last frame, a firing only counts as a turn if it
+    // spent one
+    last_charges: u8,
+}
+
+// What the player has seen each wave button do to each ghost in the legend. Only firings with a
+// single button on tell us anything for certain, so those are the only ones written down, and
+// only when every ghost of that kind moved the same number of lanes
+#[derive(Resource, Default)]
+struct DeductionNotebook {
+    open: bool,
+    // one row per legend entry, one lane change per button at dial strength 1
+    observations: Vec<[Option<i8>; 5]>,
+}
+
+// The player's own guesses at what each wave button does: which body or hat it pushes and
+// which way. Purely for the player, nothing in the game reads them back
+#[derive(Resource, Default)]
+struct ButtonNotes {
+    marks: [Vec<NoteMark>; 5],
+    picker_button: Option<usize>,
+}
+
+#[derive(Clone, Copy)]
+struct NoteMark {
+    tag: GhostTag,
+    // +1 for right, -1 for left
+    direction: i8,
+}
+
+#[derive(Resource)]
+struct EndlessSpawner {
+    firings_until_wave: u8,
+}
+
+#[derive(Resource, Serialize, Deserialize, Default)]
+#[serde(default)]
+struct SaveData {
+    endless_high_scores: Vec<u32>,
+    daily_result: Option<DailyResult>,
+    accessibility: AccessibilitySettings,
+    // a code from `LANGUAGES`, the system's language is used until one is picked
+    language: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Default, Clone, Copy)]
+#[serde(default)]
+struct AccessibilitySettings {
+    // colorblind safe ghost tints, a dimmed background and shapes over the remote's lights and
+    // switches
+    high_contrast: bool,
+    // ON/OFF and INV/NORM written next to the remote's lights and switches
+    state_labels: bool,
+    // no capture flash, calmer bobbing and fewer wave particles
+    reduced_motion: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+struct DailyResult {
+    day: u64,
+    won: bool,
+    charges_used: u8,
+    summary: String,
+}
+
+// Tracks today's puzzle while it's being played, one emoji per firing or capture
+#[derive(Resource, Default)]
+struct DailyChallenge {
+    day: u64,
+    cells: Vec<&'static str>,
+    last_target_distance: u32,
+    last_charges: u8,
+}
+impl DailyChallenge {
+    fn summary(&self, won: bool, charges_used: u8, strings: &Strings) -> String {
+        let outcome = if won {
+            strings.format("daily.summary_won", &[&charges_used])
+        } else {
+            strings.get("daily.summary_lost").to_string()
+        };
+        let mut summary = strings.format(
+            "daily.summary",
+            &[&(self.day.saturating_sub(DAILY_FIRST_DAY) + 1), &outcome]);
+        for row in self.cells.chunks(DAILY_SUMMARY_ROW_LENGTH) {
+            summary.push('\n');
+            summary.push_str(&row.concat());
+        }
+        return summary;
+    }
+}
+
+// The colored square each summary emoji is drawn as on the splash, the font doesn't have them
+fn daily_cell_color(cell: &str) -> Color {
+    return match cell {
+        "🟩" => Color::srgb(0.3, 0.8, 0.3),
+        "🟥" => Color::srgb(0.85, 0.25, 0.25),
+        "👻" => Color::srgb(0.75, 0.65, 0.95),
+        _ => Color::srgb(0.85, 0.85, 0.85),
+    };
+}
+
+fn current_day() -> u64 {
+    let since_epoch = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+    return since_epoch.as_secs() / (60 * 60 * 24);
+}
+
+fn daily_rng(day: u64, salt: u64) -> StdRng {
+    return StdRng::seed_from_u64(day.wrapping_mul(0x9e37_79b9_7f4a_7c15) ^ salt);
+}
+
+// Reads a file next to the game, or the browser's local storage entry of the same name on the web
+#[cfg(not(target_arch = "wasm32"))]
+fn read_stored_text(name: &str) -> Option<String> {
+    return std::fs::read_to_string(name).ok();
+}
+
+#[cfg(target_arch = "wasm32")]
+fn read_stored_text(name: &str) -> Option<String> {
+    let storage = web_sys::window()?.local_storage().ok()??;
+    return storage.get_item(name).ok()?;
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn write_stored_text(name: &str, contents: &str) -> Result<(), String> {
+    return std::fs::write(name, contents).map_err(|err| err.to_string());
+}
+
+#[cfg(target_arch = "wasm32")]
+fn write_stored_text(name: &str, contents: &str) -> Result<(), String> {
+    let storage = web_sys::window()
+        .and_then(|window| window.local_storage().ok().flatten())
+        .ok_or("local storage isn't available")?;
+    return storage.set_item(name, contents).map_err(|err| format!("{err:?}"));
+}
+impl SaveData {
+    fn load() -> SaveData {
+        let Some(contents) = read_stored_text(SAVE_FILE_PATH) else {
+            return SaveData::default();
+        };
+        return ron::from_str(&contents).unwrap_or_else(|err| {
+            warn!("Ignoring unreadable save file: {err}");
+            return SaveData::default();
+        });
+    }
+
+    fn save(&self) {
+        let result = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+            .map_err(|err| err.to_string())
+            .and_then(|contents| write_stored_text(SAVE_FILE_PATH, &contents));
+        if let Err(err) = result {
+            warn!("Couldn't write the save file: {err}");
+        }
+    }
+
+    // Returns the score's place in the table, if it made it in
+    fn record_endless_score(&mut self, score: u32) -> Option<usize> {
+        let rank = self.endless_high_scores.iter()
+            .position(|&high_score| score > high_score)
+            .unwrap_or(self.endless_high_scores.len());
+        if rank >= HIGH_SCORE_TABLE_SIZE {
+            return None;
+        }
+        self.endless_high_scores.insert(rank, score);
+        self.endless_high_scores.truncate(HIGH_SCORE_TABLE_SIZE);
+        return Some(rank);
+    }
+}
+
 #[derive(Resource, Default)]
 struct Sprites {
     //by body, then by hat
-    ghosts: Option<[[Handle<Image>; 14]; 8]>,
+    ghosts: Option<Vec<Vec<Handle<Image>>>>,
+    // every body without a hat, indexed by body
+    bare_ghosts: Option<Vec<Handle<Image>>>,
     background: Option<Handle<Image>>,
     frame: Option<Handle<Image>>,
-    frame_counter: Option<[Handle<Image>; 11]>,
+    // every counter digit is a panel sitting in its own slice of the counter's frame
+    counter_frame_mesh: Option<Handle<Mesh>>,
+    counter_frame_material: Option<Handle<ColorMaterial>>,
+    counter_panel_mesh: Option<Handle<Mesh>>,
+    counter_panel_material: Option<Handle<ColorMaterial>>,
     remote_base: Option<Handle<Image>>,
     remote_dial: Option<[Handle<Image>; 3]>,
     // by wave, then by state
@@ -197,20 +590,553 @@ struct Sprites {
     flash_mesh: Option<Handle<Mesh>>,
     flash_material: Option<Handle<ColorMaterial>>,
 }
+impl Sprites {
+    fn ghost(&self, catalog: &GhostCatalog, tags: &GhostTags) -> Sprite {
+        let ghost_sprites = self.ghosts.as_ref().expect("Sprites should be loaded");
+        let body_idx = catalog.body_index(tags.body_tag);
+        let hat_idx = catalog.hat_index(tags.hat_tag);
+        return Sprite {
+            image: ghost_sprites[body_idx][hat_idx].clone(),
+            color: catalog.tint(tags.color_tag, false),
+            ..default()
+        };
+    }
+}
 
 #[derive(Resource, Default)]
 struct AudioHandles {
     music: Option<Handle<AudioSource>>,
-    rectified: Option<Handle<AudioSource>>,
-    rectified_inv: Option<Handle<AudioSource>>,
-    sawtooth: Option<Handle<AudioSource>>,
-    sawtooth_inv: Option<Handle<AudioSource>>,
-    sine: Option<Handle<AudioSource>>,
-    sine_inv: Option<Handle<AudioSource>>,
-    square: Option<Handle<AudioSource>>,
-    square_inv: Option<Handle<AudioSource>>,
-    triangle: Option<Handle<AudioSource>>,
-    triangle_inv: Option<Handle<AudioSource>>,
+}
+
+// Knobs shared with the music while it plays, so the mix can follow the game one sample at a
+// time without ever restarting the track
+#[derive(Resource, Clone, Default)]
+struct MusicControls {
+    // both are f32s stored as bits, from 0 to 1
+    tension: Arc<AtomicU32>,
+    muffle: Arc<AtomicU32>,
+    // set to one of the `MusicSting`s, the music clears it once it starts playing it
+    sting: Arc<AtomicU8>,
+}
+
+#[repr(u8)]
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum MusicSting {
+    None = 0,
+    Win = 1,
+    Lose = 2,
+}
+
+// The music track with a couple of synthesized stems layered over it: a heartbeat that creeps
+// in as the charges run down, a shimmer for the last few, and a sting to finish the round
+#[derive(Asset, TypePath, Clone)]
+struct AdaptiveMusic {
+    track: AudioSource,
+    controls: MusicControls,
+}
+impl Decodable for AdaptiveMusic {
+    type DecoderItem = f32;
+    type Decoder = AdaptiveMusicDecoder;
+
+    fn decoder(&self) -> Self::Decoder {
+        let track_decoder = self.track.decoder();
+        let channels = track_decoder.channels();
+        let sample_rate = track_decoder.sample_rate();
+        return AdaptiveMusicDecoder {
+            track: self.track.clone(),
+            track_decoder,
+            controls: self.controls.clone(),
+            channels,
+            sample_rate,
+            channel_idx: 0,
+            frame_idx: 0,
+            tension: 0.0,
+            muffle: 0.0,
+            filtered: vec![0.0; channels as usize],
+            stems: 0.0,
+            sting: MusicSting::None,
+            sting_start: 0,
+            beat_phase: 0.0,
+            heartbeat_phase: 0.0,
+            shimmer_phase: 0.0,
+            shimmer_wobble_phase: 0.0,
+        };
+    }
+}
+
+struct AdaptiveMusicDecoder {
+    track: AudioSource,
+    track_decoder: <AudioSource as Decodable>::Decoder,
+    controls: MusicControls,
+    channels: u16,
+    sample_rate: u32,
+    channel_idx: u16,
+    frame_idx: u64,
+    // where the mix currently is, chasing the values in `controls`
+    tension: f32,
+    muffle: f32,
+    // low pass state for each channel
+    filtered: Vec<f32>,
+    // the synthesized stems for the current frame, shared by every channel
+    stems: f32,
+    sting: MusicSting,
+    sting_start: u64,
+    // each oscillator keeps its own phase in 0..1, the music plays for as long as the game is
+    // open and an f32 clock counting from the start gets too coarse to play in tune
+    beat_phase: f32,
+    heartbeat_phase: f32,
+    shimmer_phase: f32,
+    shimmer_wobble_phase: f32,
+}
+impl AdaptiveMusicDecoder {
+    fn next_track_sample(&mut self) -> f32 {
+        if let Some(sample) = self.track_decoder.next() {
+            return sample.to_f32();
+        }
+        // loop back around to the start of the track
+        self.track_decoder = self.track.decoder();
+        return self.track_decoder.next().map(|sample| sample.to_f32()).unwrap_or(0.0);
+    }
+
+    // Moves the mix one frame closer to where the game wants it and works out the stems
+    fn advance_frame(&mut self) {
+        let step = 1.0 / (MUSIC_CROSSFADE_SECONDS * self.sample_rate as f32);
+        let target_tension = f32::from_bits(self.controls.tension.load(Ordering::Relaxed));
+        let target_muffle = f32::from_bits(self.controls.muffle.load(Ordering::Relaxed));
+        self.tension += (target_tension - self.tension).clamp(-step, step);
+        self.muffle += (target_muffle - self.muffle).clamp(-step, step);
+
+        let requested_sting = self.controls.sting.swap(MusicSting::None as u8, Ordering::Relaxed);
+        if requested_sting == MusicSting::Win as u8 {
+            self.sting = MusicSting::Win;
+            self.sting_start = self.frame_idx;
+        } else if requested_sting == MusicSting::Lose as u8 {
+            self.sting = MusicSting::Lose;
+            self.sting_start = self.frame_idx;
+        }
+
+        let tau = 2.0 * std::f32::consts::PI;
+        let frame_secs = 1.0 / self.sample_rate as f32;
+        let heartbeat = (tau * self.heartbeat_phase).sin() * (1.0 - self.beat_phase).powi(6);
+        let heartbeat_gain = ((self.tension - 0.3) / 0.5).clamp(0.0, 1.0) * 0.5;
+        let shimmer = (4.0 * self.shimmer_phase - 2.0).abs() - 1.0;
+        let shimmer = shimmer * (0.5 + 0.5 * (tau * self.shimmer_wobble_phase).sin());
+        let shimmer_gain = ((self.tension - 0.7) / 0.3).clamp(0.0, 1.0) * 0.1;
+        self.stems = heartbeat * heartbeat_gain + shimmer * shimmer_gain + self.sting_sample();
+
+        // the heartbeat speeds up along with the tension
+        self.beat_phase = (self.beat_phase + (1.0 + self.tension) * frame_secs).fract();
+        self.heartbeat_phase = (self.heartbeat_phase + 55.0 * frame_secs).fract();
+        self.shimmer_phase = (self.shimmer_phase + 660.0 * frame_secs).fract();
+        self.shimmer_wobble_phase = (self.shimmer_wobble_phase + 6.0 * frame_secs).fract();
+        self.frame_idx += 1;
+    }
+
+    fn sting_sample(&mut self) -> f32 {
+        let notes: &[f32] = match self.sting {
+            MusicSting::None => return 0.0,
+            MusicSting::Win => &MUSIC_STING_WIN_NOTES,
+            MusicSting::Lose => &MUSIC_STING_LOSE_NOTES,
+        };
+        let sting_t = (self.frame_idx - self.sting_start) as f32 / self.sample_rate as f32;
+        let note_idx = (sting_t / MUSIC_STING_NOTE_SECONDS) as usize;
+        // let the last note ring out a bit longer
+        let note_idx = note_idx.min(notes.len() - 1);
+        let note_t = sting_t - note_idx as f32 * MUSIC_STING_NOTE_SECONDS;
+        let decay = (-4.0 * note_t).exp();
+        if decay < 0.001 {
+            self.sting = MusicSting::None;
+            return 0.0;
+        }
+        return (2.0 * std::f32::consts::PI * notes[note_idx] * sting_t).sin() * decay * 0.3;
+    }
+}
+impl Iterator for AdaptiveMusicDecoder {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        if self.channel_idx == 0 {
+            self.advance_frame();
+        }
+        let channel = self.channel_idx as usize;
+        self.channel_idx = (self.channel_idx + 1) % self.channels;
+
+        let track_sample = self.next_track_sample();
+        // muffling is a low pass that closes down as the muffle goes up
+        let smoothing = 1.0 - 0.93 * self.muffle;
+        self.filtered[channel] += smoothing * (track_sample - self.filtered[channel]);
+        return Some((self.filtered[channel] + self.stems).clamp(-1.0, 1.0));
+    }
+}
+impl Source for AdaptiveMusicDecoder {
+    fn current_frame_len(&self) -> Option<usize> {
+        return None;
+    }
+
+    fn channels(&self) -> u16 {
+        return self.channels;
+    }
+
+    fn sample_rate(&self) -> u32 {
+        return self.sample_rate;
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        return None;
+    }
+}
+
+fn update_music_mix(
+    controls: Res<MusicControls>,
+    resources: Res<PlayerResources>,
+    state: Res<State<GameState>>,
+    mut on_win: MessageReader<GameWon>,
+    mut on_lose: MessageReader<GameLost>,
+) {
+    let tension = if *state.get() == GameState::Game {
+        1.0 - resources.charges as f32 / 10.0
+    } else {
+        0.0
+    };
+    let muffle = if *state.get() == GameState::GameEnd { 1.0 } else { 0.0 };
+    controls.tension.store(f32::to_bits(tension.clamp(0.0, 1.0)), Ordering::Relaxed);
+    controls.muffle.store(f32::to_bits(muffle), Ordering::Relaxed);
+    if !on_win.is_empty() {
+        controls.sting.store(MusicSting::Win as u8, Ordering::Relaxed);
+    } else if !on_lose.is_empty() {
+        controls.sting.store(MusicSting::Lose as u8, Ordering::Relaxed);
+    }
+    on_win.clear();
+    on_lose.clear();
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+enum SfxSound {
+    Click,
+    Toggle,
+    Dial,
+    Handle,
+    Capture,
+    WanderOff,
+    Whoosh,
+}
+impl SfxSound {
+    const ALL: [SfxSound; 7] = [
+        SfxSound::Click,
+        SfxSound::Toggle,
+        SfxSound::Dial,
+        SfxSound::Handle,
+        SfxSound::Capture,
+        SfxSound::WanderOff,
+        SfxSound::Whoosh,
+    ];
+
+    fn duration(&self) -> f32 {
+        return match self {
+            SfxSound::Click => 0.05,
+            SfxSound::Toggle => 0.09,
+            SfxSound::Dial => 0.12,
+            SfxSound::Handle => 0.3,
+            SfxSound::Capture => 0.45,
+            SfxSound::WanderOff => 0.8,
+            SfxSound::Whoosh => 0.35,
+        };
+    }
+
+    fn volume(&self) -> f32 {
+        return match self {
+            SfxSound::Click | SfxSound::Toggle | SfxSound::Dial => 0.3,
+            SfxSound::Handle => 0.5,
+            SfxSound::Capture => 0.35,
+            SfxSound::WanderOff => 0.3,
+            SfxSound::Whoosh => 0.25,
+        };
+    }
+}
+
+// Synthesized up front so each sound only has to be played
+#[derive(Resource, Default)]
+struct SfxBank {
+    sounds: HashMap<SfxSound, Handle<SfxSynth>>,
+}
+
+#[derive(Message)]
+struct PlaySfx {
+    sound: SfxSound,
+    // where in the game area the sound comes from, it plays dead center without one
+    position: Option<Vec2>,
+}
+
+// One of the short sound effects, always spawned alongside `Sfx` so they get cleaned up with
+// the rest of the round's sounds
+#[derive(Component)]
+struct SfxVoice {
+    sound: SfxSound,
+}
+
+#[derive(Asset, TypePath, Clone)]
+struct SfxSynth {
+    sound: SfxSound,
+}
+impl Decodable for SfxSynth {
+    type DecoderItem = f32;
+    type Decoder = SfxSynthDecoder;
+
+    fn decoder(&self) -> Self::Decoder {
+        return SfxSynthDecoder {
+            sound: self.sound,
+            total_samples: (self.sound.duration() * WAVE_SYNTH_SAMPLE_RATE as f32) as u64,
+            sample_idx: 0,
+            phase: 0.0,
+            noise: 0x9e3779b9,
+        };
+    }
+}
+
+struct SfxSynthDecoder {
+    sound: SfxSound,
+    total_samples: u64,
+    sample_idx: u64,
+    phase: f32,
+    // xorshift state, so the noise doesn't need an rng
+    noise: u32,
+}
+impl SfxSynthDecoder {
+    fn next_noise(&mut self) -> f32 {
+        self.noise ^= self.noise << 13;
+        self.noise ^= self.noise >> 17;
+        self.noise ^= self.noise << 5;
+        return self.noise as f32 / u32::MAX as f32 * 2.0 - 1.0;
+    }
+}
+impl Iterator for SfxSynthDecoder {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        if self.sample_idx >= self.total_samples {
+            return None;
+        }
+        let tau = 2.0 * std::f32::consts::PI;
+        let t = self.sample_idx as f32 / WAVE_SYNTH_SAMPLE_RATE as f32;
+        // how far through the sound we are, from 0 to 1
+        let progress = self.sample_idx as f32 / self.total_samples as f32;
+        let (frequency, sample) = match self.sound {
+            SfxSound::Click => (1400.0, (tau * self.phase).sin() * (-80.0 * t).exp()),
+            SfxSound::Toggle => {
+                let frequency = if progress < 0.5 { 900.0 } else { 1350.0 };
+                (frequency, (4.0 * (self.phase - 0.5).abs() - 1.0) * (1.0 - progress))
+            },
+            SfxSound::Dial => {
+                // a few quick ratchet clicks
+                let ratchet = (-60.0 * (t % 0.04)).exp();
+                (700.0, (tau * self.phase).sin() * ratchet * (1.0 - progress))
+            },
+            SfxSound::Handle => {
+                // a clunk of noise, then the charge winding down
+                let clunk = self.next_noise() * (-40.0 * t).exp();
+                let frequency = 220.0 - 150.0 * progress;
+                (frequency, clunk * 0.6 + (tau * self.phase).sin() * (1.0 - progress) * 0.7)
+            },
+            SfxSound::Capture => {
+                let frequency = 400.0 + 1200.0 * progress * progress;
+                let shimmer = 0.75 + 0.25 * (tau * 18.0 * t).sin();
+                (frequency, (tau * self.phase).sin() * shimmer * (1.0 - progress))
+            },
+            SfxSound::WanderOff => {
+                // a wobbly "ooooo" trailing away
+                let frequency = (520.0 - 260.0 * progress) * (1.0 + 0.04 * (tau * 6.0 * t).sin());
+                let swell = (progress * 8.0).min(1.0) * (1.0 - progress);
+                (frequency, (tau * self.phase).sin() * swell)
+            },
+            SfxSound::Whoosh => {
+                // noise that swells and fades, with a faint rising tone underneath
+                let swell = (progress * std::f32::consts::PI).sin();
+                let frequency = 180.0 + 220.0 * progress;
+                (frequency, (self.next_noise() * 0.6 + (tau * self.phase).sin() * 0.2) * swell)
+            },
+        };
+        let fade_samples = WAVE_SYNTH_FADE_SECONDS * WAVE_SYNTH_SAMPLE_RATE as f32;
+        let fade_out = ((self.total_samples - self.sample_idx) as f32 / fade_samples).min(1.0);
+        self.phase = (self.phase + frequency / WAVE_SYNTH_SAMPLE_RATE as f32).fract();
+        self.sample_idx += 1;
+        return Some(sample * fade_out);
+    }
+}
+impl Source for SfxSynthDecoder {
+    fn current_frame_len(&self) -> Option<usize> {
+        return None;
+    }
+
+    fn channels(&self) -> u16 {
+        return 1;
+    }
+
+    fn sample_rate(&self) -> u32 {
+        return WAVE_SYNTH_SAMPLE_RATE;
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        return Some(Duration::from_secs_f32(self.sound.duration()));
+    }
+}
+
+// Drops any sound that would pile up past the voice limits, and turns each one down the more
+// copies of it are already playing
+fn play_sfx(
+    mut on_sfx: MessageReader<PlaySfx>,
+    sfx_bank: Res<SfxBank>,
+    voices: Query<&SfxVoice>,
+    mut commands: Commands,
+) {
+    if on_sfx.is_empty() {
+        return;
+    }
+    let mut voice_count = voices.iter().count();
+    let mut sound_counts = HashMap::<SfxSound, usize>::new();
+    for voice in voices {
+        *sound_counts.entry(voice.sound).or_default() += 1;
+    }
+    let mut rng = rand::rng();
+    for request in on_sfx.read() {
+        let sound_count = sound_counts.entry(request.sound).or_default();
+        if voice_count >= SFX_MAX_VOICES || *sound_count >= SFX_MAX_VOICES_PER_SOUND {
+            continue;
+        }
+        let Some(handle) = sfx_bank.sounds.get(&request.sound) else { continue };
+        let speed = 1.0 + rng.random_range(-SFX_PITCH_VARIATION..SFX_PITCH_VARIATION);
+        let volume = request.sound.volume() / (1.0 + *sound_count as f32);
+        let mut settings = PlaybackSettings::ONCE
+            .with_speed(speed)
+            .with_volume(bevy::audio::Volume::Linear(volume));
+        if request.position.is_some() {
+            settings = settings.with_spatial(true);
+            // half the game area's width away counts as all the way over to one side
+            settings.spatial_scale = Some(SpatialScale::new_2d(2.0 / GAME_AREA_WIDTH));
+        }
+        commands.spawn((
+            Sfx,
+            SfxVoice { sound: request.sound },
+            AudioPlayer(handle.clone()),
+            settings,
+            Transform::from_translation(request.position.unwrap_or_default().extend(0.0)),
+            Lifetime::new(request.sound.duration() / speed),
+        ));
+        *sound_count += 1;
+        voice_count += 1;
+    }
+}
+
+// The remote's waves, mixed down into one sound. Each enabled button plays its own shape on its
+// own note, the dial multiplies the pitch and inverted buttons play upside down
+#[derive(Asset, TypePath, Clone)]
+struct WaveSynth {
+    // (button index, inverted)
+    voices: Vec<(usize, bool)>,
+    pitch: f32,
+    duration: Option<f32>,
+}
+impl WaveSynth {
+    fn from_remote(ghost_wave: &GhostWaveConfig, duration: Option<f32>) -> WaveSynth {
+        let voices = ghost_wave.buttons.iter()
+            .enumerate()
+            .filter(|(_, button)| button.enabled)
+            .map(|(idx, button)| (idx, button.inverted))
+            .collect();
+        return WaveSynth {
+            voices,
+            pitch: ghost_wave.dial_strength as f32,
+            duration,
+        };
+    }
+}
+impl Decodable for WaveSynth {
+    type DecoderItem = f32;
+    type Decoder = WaveSynthDecoder;
+
+    fn decoder(&self) -> Self::Decoder {
+        return WaveSynthDecoder {
+            voices: self.voices.clone(),
+            phases: vec![0.0; self.voices.len()],
+            pitch: self.pitch,
+            total_samples: self.duration.map(|seconds| (seconds * WAVE_SYNTH_SAMPLE_RATE as f32) as u64),
+            sample_idx: 0,
+        };
+    }
+}
+
+struct WaveSynthDecoder {
+    voices: Vec<(usize, bool)>,
+    // where each voice is through its current cycle, from 0 to 1
+    phases: Vec<f32>,
+    pitch: f32,
+    total_samples: Option<u64>,
+    sample_idx: u64,
+}
+impl WaveSynthDecoder {
+    // One cycle of each button's wave shape, in the same order as the buttons
+    fn oscillator(button_idx: usize, phase: f32) -> f32 {
+        let tau = 2.0 * std::f32::consts::PI;
+        return match button_idx {
+            // rectified sine, shifted back down to sit around zero
+            0 => (tau * phase).sin().abs() * 2.0 - 1.0,
+            1 => phase * 2.0 - 1.0,
+            2 => (tau * phase).sin(),
+            // square and saw are a lot louder than the rest at the same amplitude
+            3 => if phase < 0.5 { 0.6 } else { -0.6 },
+            _ => 4.0 * (phase - 0.5).abs() - 1.0,
+        };
+    }
+
+    // Short fades at either end so it doesn't click on or off
+    fn envelope(&self) -> f32 {
+        let fade_samples = WAVE_SYNTH_FADE_SECONDS * WAVE_SYNTH_SAMPLE_RATE as f32;
+        let fade_in = (self.sample_idx as f32 / fade_samples).min(1.0);
+        let fade_out = match self.total_samples {
+            Some(total) => ((total - self.sample_idx) as f32 / fade_samples).min(1.0),
+            None => 1.0,
+        };
+        return fade_in * fade_out;
+    }
+}
+impl Iterator for WaveSynthDecoder {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        if self.total_samples.is_some_and(|total| self.sample_idx >= total) {
+            return None;
+        }
+        if self.voices.is_empty() {
+            self.sample_idx += 1;
+            return Some(0.0);
+        }
+        let mut sample = 0.0;
+        for ((button_idx, inverted), phase) in self.voices.iter().zip(self.phases.iter_mut()) {
+            let voice = WaveSynthDecoder::oscillator(*button_idx, *phase);
+            sample += if *inverted { -voice } else { voice };
+            *phase = (*phase + WAVE_SYNTH_BASE_FREQUENCIES[*button_idx] * self.pitch / WAVE_SYNTH_SAMPLE_RATE as f32).fract();
+        }
+        sample *= self.envelope() / self.voices.len() as f32;
+        self.sample_idx += 1;
+        return Some(sample);
+    }
+}
+impl Source for WaveSynthDecoder {
+    fn current_frame_len(&self) -> Option<usize> {
+        return None;
+    }
+
+    fn channels(&self) -> u16 {
+        return 1;
+    }
+
+    fn sample_rate(&self) -> u32 {
+        return WAVE_SYNTH_SAMPLE_RATE;
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        return self.total_samples
+            .map(|total| Duration::from_secs_f64(total as f64 / WAVE_SYNTH_SAMPLE_RATE as f64));
+    }
 }
 
 #[derive(Resource)]
@@ -223,10 +1149,12 @@ struct UIEnabled {
 struct PlayerResources {
     charges: u8,
     reputation: u8,
+    score: u32,
 }
 
+#[derive(Clone)]
 struct ButtonConfig {
-    interactions: [Option<i8>; 4],
+    interactions: [Option<GhostTag>; 4],
     strength: i8,
     inverted: bool,
     enabled: bool,
@@ -238,92 +1166,218 @@ struct GhostWaveConfig {
     dial_strength: u8,
 }
 
-#[derive(Component, Copy, Clone)]
+#[derive(Component, Copy, Clone, PartialEq, Eq)]
 struct GhostTags {
-    body_tag: i8,
-    hat_tag: i8,
+    body_tag: GhostTag,
+    hat_tag: GhostTag,
+    color_tag: GhostTag,
 }
 impl GhostTags {
-    fn new(body: i8, hat: i8) -> GhostTags {
+    fn new(body: GhostTag, hat: GhostTag, color: GhostTag) -> GhostTags {
         GhostTags {
             body_tag: body,
             hat_tag: hat,
+            color_tag: color,
         }
     }
+
+    fn tags(&self) -> [GhostTag; 3] {
+        [self.body_tag, self.hat_tag, self.color_tag]
+    }
 }
 
 #[derive(Resource)]
 struct TargetGhostTags {
-    target: GhostTags,
-    others: [Option<GhostTags>; 8],
-    all_tags: [Option<i8>; 8],
-    other_tags: [Option<i8>; 8],
+    targets: Vec<GhostTags>,
+    // reputation for each target captured, lines up with `targets`
+    rewards: Vec<u8>,
+    others: Vec<GhostTags>,
+    // lines up with `others`
+    other_behaviours: Vec<Option<GhostBehaviour>>,
+    all_tags: Vec<GhostTag>,
+}
+impl TargetGhostTags {
+    fn is_target(&self, tags: &GhostTags) -> bool {
+        self.targets.contains(tags)
+    }
 }
 
-#[derive(Component)]
-struct TargetGhostDisplay;
+#[derive(Deserialize)]
+struct GhostCatalogEntry {
+    name: String,
+    sprite: String,
+}
 
-#[derive(Resource)]
-struct LoadingProgress {
-    total: usize,
-    current: usize,
+#[derive(Deserialize)]
+struct GhostColorEntry {
+    name: String,
+    tint: (f32, f32, f32),
+    high_contrast_tint: (f32, f32, f32),
 }
 
-fn update_loading_progress(
-    assets: Res<AssetServer>,
-    sprites: Res<Sprites>,
-    audio: Res<AudioHandles>,
-    mut loading_progress: ResMut<LoadingProgress>,
-    mut game_state: ResMut<NextState<GameState>>,
-) {
-    let mut current_progress = 0;
-    if asset_is_loaded(&assets, &audio.music) {
-        current_progress += 1;
+#[derive(Deserialize)]
+struct CaptureZoneEntry {
+    lane_offset: i8,
+    targets: usize,
+}
+
+#[derive(Deserialize)]
+struct CaptureZoneLayout {
+    weight: u32,
+    zones: Vec<CaptureZoneEntry>,
+}
+
+// All of the bodies, hats and colors a ghost can be built from, loaded from
+// `ghosts/catalog.ron`. Hats are tagged first in the order they're listed, then bodies and
+// colors follow on from there. The capture zone layouts a round can pick from live there too.
+#[derive(Resource, Deserialize)]
+struct GhostCatalog {
+    sprite_path: String,
+    bare_sprite_path: String,
+    bodies: Vec<GhostCatalogEntry>,
+    hats: Vec<GhostCatalogEntry>,
+    colors: Vec<GhostColorEntry>,
+    // leaving these out rolls the zones at random instead
+    #[serde(default)]
+    capture_zone_layouts: Vec<CaptureZoneLayout>,
+}
+impl GhostCatalog {
+    fn load() -> GhostCatalog {
+        let catalog: GhostCatalog = ron::from_str(include_str!("../assets/ghosts/catalog.ron"))
+            .expect("Ghost catalog should be valid");
+        assert!(
+            catalog.bodies.len() + catalog.hats.len() + catalog.colors.len() <= GhostTag::MAX as usize,
+            "Ghost catalog has more entries than there are tags");
+        for layout in catalog.capture_zone_layouts.iter() {
+            assert!(
+                !layout.zones.is_empty() && layout.zones.len() <= CAPTURE_ZONE_COLORS.len(),
+                "Capture zone layouts should have between 1 and {} zones", CAPTURE_ZONE_COLORS.len());
+            assert!(
+                layout.zones.iter().all(|zone| {
+                    let lane = LANE_LAYOUT_CENTER_LANE as i8 + zone.lane_offset;
+                    return zone.targets > 0
+                        && lane >= LANE_LAYOUT_BUFFER_LANES as i8
+                        && lane < (LANE_LAYOUT_LANE_COUNT - LANE_LAYOUT_BUFFER_LANES) as i8;
+                }),
+                "Capture zones should hold at least one target and sit outside the buffer lanes");
+            assert!(
+                layout.zones.iter().map(|zone| zone.targets).sum::<usize>() <= CAPTURE_ZONE_MAX_TARGETS,
+                "Capture zone layouts can't ask for more than {CAPTURE_ZONE_MAX_TARGETS} targets");
+            let mut offsets: Vec<i8> = layout.zones.iter().map(|zone| zone.lane_offset).collect();
+            offsets.sort();
+            offsets.dedup();
+            assert!(
+                offsets.len() == layout.zones.len(),
+                "Capture zones in the same layout need their own lanes");
+        }
+        return catalog;
     }
-    if asset_is_loaded(&assets, &audio.rectified) {
-        current_progress += 1;
+
+    fn ghost_count(&self) -> usize {
+        self.bodies.len() * self.hats.len()
     }
-    if asset_is_loaded(&assets, &audio.rectified_inv) {
-        current_progress += 1;
+
+    fn hat_tags(&self) -> std::ops::Range<GhostTag> {
+        0..self.hats.len() as GhostTag
     }
-    if asset_is_loaded(&assets, &audio.sawtooth) {
-        current_progress += 1;
+
+    fn body_tags(&self) -> std::ops::Range<GhostTag> {
+        let first = self.hats.len() as GhostTag;
+        first..first + self.bodies.len() as GhostTag
     }
-    if asset_is_loaded(&assets, &audio.sawtooth_inv) {
-        current_progress += 1;
+
+    fn color_tags(&self) -> std::ops::Range<GhostTag> {
+        let first = (self.hats.len() + self.bodies.len()) as GhostTag;
+        first..first + self.colors.len() as GhostTag
     }
-    if asset_is_loaded(&assets, &audio.sine) {
-        current_progress += 1;
+
+    fn hat_index(&self, tag: GhostTag) -> usize {
+        debug_assert!(self.hat_tags().contains(&tag), "Tag {tag} is not a hat");
+        return tag as usize;
     }
-    if asset_is_loaded(&assets, &audio.sine_inv) {
-        current_progress += 1;
+
+    fn body_index(&self, tag: GhostTag) -> usize {
+        debug_assert!(self.body_tags().contains(&tag), "Tag {tag} is not a body");
+        return tag as usize - self.hats.len();
     }
-    if asset_is_loaded(&assets, &audio.square) {
-        current_progress += 1;
+
+    fn color_index(&self, tag: GhostTag) -> usize {
+        debug_assert!(self.color_tags().contains(&tag), "Tag {tag} is not a color");
+        return tag as usize - self.hats.len() - self.bodies.len();
     }
-    if asset_is_loaded(&assets, &audio.square_inv) {
-        current_progress += 1;
+
+    fn tint(&self, tag: GhostTag, high_contrast: bool) -> Color {
+        let color = &self.colors[self.color_index(tag)];
+        let (r, g, b) = if high_contrast { color.high_contrast_tint } else { color.tint };
+        return Color::srgb(r, g, b);
+    }
+
+    fn display_name(&self, tags: &GhostTags, strings: &Strings) -> String {
+        let body = &self.bodies[self.body_index(tags.body_tag)];
+        let hat = &self.hats[self.hat_index(tags.hat_tag)];
+        let color = &self.colors[self.color_index(tags.color_tag)];
+        return strings.format("ghost.description", &[
+            &strings.ghost_name(&color.name),
+            &strings.ghost_name(&body.name),
+            &strings.ghost_name(&hat.name),
+        ]);
+    }
+
+    fn sprite_path(&self, body_idx: usize, hat_idx: usize) -> String {
+        self.sprite_path
+            .replace("{body}", &self.bodies[body_idx].sprite)
+            .replace("{hat}", &self.hats[hat_idx].sprite)
+    }
+
+    fn bare_sprite_path(&self, body_idx: usize) -> String {
+        self.bare_sprite_path.replace("{body}", &self.bodies[body_idx].sprite)
     }
-    if asset_is_loaded(&assets, &audio.triangle) {
+}
+
+#[derive(Component)]
+struct TargetGhostDisplay;
+
+#[derive(Component)]
+struct TargetClearedTick {
+    target_idx: usize,
+}
+
+#[derive(Resource)]
+struct LoadingProgress {
+    total: usize,
+    current: usize,
+}
+
+fn update_loading_progress(
+    assets: Res<AssetServer>,
+    sprites: Res<Sprites>,
+    audio: Res<AudioHandles>,
+    locale: Res<LocaleHandles>,
+    mut loading_progress: ResMut<LoadingProgress>,
+    mut game_state: ResMut<NextState<GameState>>,
+) {
+    let mut current_progress = 0;
+    if asset_is_loaded(&assets, &audio.music) {
         current_progress += 1;
     }
-    if asset_is_loaded(&assets, &audio.triangle_inv) {
+    if asset_is_loaded(&assets, &locale.font) {
         current_progress += 1;
     }
+    current_progress += count_loaded_assets(&assets, &locale.tables);
     for group in sprites.ghosts.iter() {
         for handles in group {
             current_progress += count_loaded_assets(&assets, handles);
         }
     }
+    for handles in sprites.bare_ghosts.iter() {
+        current_progress += count_loaded_assets(&assets, handles);
+    }
     if asset_is_loaded(&assets, &sprites.background) {
         current_progress += 1;
     }
     if asset_is_loaded(&assets, &sprites.frame) {
         current_progress += 1;
     }
-    for handles in sprites.frame_counter.iter() {
-        current_progress += count_loaded_assets(&assets, handles);
-    }
     if asset_is_loaded(&assets, &sprites.remote_base) {
         current_progress += 1;
     }
@@ -366,7 +1420,7 @@ fn update_loading_progress(
 
     loading_progress.current = current_progress;
     if loading_progress.current >= loading_progress.total {
-        game_state.set(GameState::Game);
+        game_state.set(GameState::Menu);
     }
 }
 
@@ -397,45 +1451,215 @@ fn asset_is_loaded<T: Asset>(asset_server: &AssetServer, image_handle: &Option<H
     return asset_server.get_load_state(handle).is_some_and(|val| val.is_loaded());
 }
 
+// Every string the player sees in one language, loaded from `locale/*.strings.ron`
+#[derive(Asset, TypePath, Deserialize, Default, Clone)]
+struct StringTable {
+    language: String,
+    strings: HashMap<String, String>,
+    // catalog names to display names, anything missing shows as it's written in the catalog
+    ghost_names: HashMap<String, String>,
+}
+
+#[derive(Default)]
+struct StringTableLoader;
+impl AssetLoader for StringTableLoader {
+    type Asset = StringTable;
+    type Settings = ();
+    type Error = std::io::Error;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<StringTable, std::io::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        return ron::de::from_bytes(&bytes).map_err(std::io::Error::other);
+    }
+
+    fn extensions(&self) -> &[&str] {
+        return &["strings.ron"];
+    }
+}
+
+#[derive(Resource, Default)]
+struct LocaleHandles {
+    // the built in font only covers ASCII, so this one stands in for it
+    font: Option<Handle<Font>>,
+    // in the same order as `LANGUAGES`
+    tables: Vec<Handle<StringTable>>,
+}
+
+// The string table for the current language, with the first language's table to fall back on
+#[derive(Resource, Default)]
+struct Strings {
+    language: usize,
+    table: StringTable,
+    fallback: StringTable,
+}
+impl Strings {
+    fn get<'a>(&'a self, key: &'a str) -> &'a str {
+        return self.table.strings.get(key)
+            .or_else(|| self.fallback.strings.get(key))
+            .map(String::as_str)
+            .unwrap_or(key);
+    }
+
+    // Fills `{0}`, `{1}` and so on in with the args, in order
+    fn format(&self, key: &str, args: &[&dyn std::fmt::Display]) -> String {
+        let mut text = self.get(key).to_string();
+        for (i, arg) in args.iter().enumerate() {
+            text = text.replace(&format!("{{{i}}}"), &arg.to_string());
+        }
+        return text;
+    }
+
+    fn ghost_name<'a>(&'a self, name: &'a str) -> &'a str {
+        return self.table.ghost_names.get(name)
+            .or_else(|| self.fallback.ghost_names.get(name))
+            .map(String::as_str)
+            .unwrap_or(name);
+    }
+
+    fn set_language(&mut self, language: usize, handles: &LocaleHandles, tables: &Assets<StringTable>) {
+        let table = |idx: usize| tables.get(&handles.tables[idx]).expect("String tables should be loaded").clone();
+        self.language = language;
+        self.table = table(language);
+        self.fallback = table(0);
+        info!("Language set to {}", self.table.language);
+    }
+}
+
+// Picks the language matching a locale like "fr-FR" or "es_ES.UTF-8", or the first one
+fn find_language(locale: &str) -> usize {
+    let code = locale.split(['-', '_', '.']).next().unwrap_or_default().to_lowercase();
+    return LANGUAGES.iter().position(|(language, _)| *language == code).unwrap_or(0);
+}
+
+// Text that's looked up in the string table, and looked up again whenever the language changes
+#[derive(Component)]
+struct LocalizedText {
+    key: &'static str,
+    args: Vec<String>,
+}
+impl LocalizedText {
+    fn new(key: &'static str) -> LocalizedText {
+        return LocalizedText {
+            key,
+            args: Vec::new(),
+        };
+    }
+
+    fn with_args(key: &'static str, args: Vec<String>) -> LocalizedText {
+        return LocalizedText {
+            key,
+            args,
+        };
+    }
+}
+
+#[derive(Component)]
+struct LocalizedGhostName(GhostTags);
+
+fn load_localization(
+    assets: Res<AssetServer>,
+    mut locale: ResMut<LocaleHandles>,
+) {
+    locale.font = Some(assets.load("fonts/DejaVuSansMono.ttf"));
+    locale.tables = LANGUAGES.iter().map(|(_, path)| assets.load(*path)).collect();
+}
+
+fn setup_localization(
+    locale: Res<LocaleHandles>,
+    tables: Res<Assets<StringTable>>,
+    save_data: Res<SaveData>,
+    mut fonts: ResMut<Assets<Font>>,
+    mut strings: ResMut<Strings>,
+) {
+    // every bit of text uses the default font, so swapping it out covers all of them at once
+    let font_handle = locale.font.as_ref().expect("Fonts should be loaded");
+    let font = fonts.get(font_handle).expect("Fonts should be loaded").clone();
+    fonts.insert(AssetId::default(), font).expect("The default font should always be insertable");
+
+    let language = save_data.language.clone()
+        .or_else(sys_locale::get_locale)
+        .map(|locale| find_language(&locale))
+        .unwrap_or(0);
+    strings.set_language(language, &locale, &tables);
+}
+
+fn toggle_language(
+    keys: Res<ButtonInput<KeyCode>>,
+    locale: Res<LocaleHandles>,
+    tables: Res<Assets<StringTable>>,
+    mut strings: ResMut<Strings>,
+    mut save_data: ResMut<SaveData>,
+) {
+    if !keys.just_pressed(KeyCode::KeyG) {
+        return;
+    }
+    let language = (strings.language + 1) % LANGUAGES.len();
+    strings.set_language(language, &locale, &tables);
+    save_data.language = Some(LANGUAGES[language].0.to_string());
+    save_data.save();
+}
+
+fn update_localized_texts(
+    strings: Res<Strings>,
+    catalog: Res<GhostCatalog>,
+    texts: Query<(Ref<LocalizedText>, &mut Text2d), Without<LocalizedGhostName>>,
+    ghost_names: Query<(Ref<LocalizedGhostName>, &mut Text2d), Without<LocalizedText>>,
+) {
+    for (localized, mut text) in texts {
+        if !strings.is_changed() && !localized.is_changed() {
+            continue;
+        }
+        let args: Vec<&dyn std::fmt::Display> = localized.args.iter()
+            .map(|arg| arg as &dyn std::fmt::Display)
+            .collect();
+        text.0 = strings.format(localized.key, &args);
+    }
+    for (ghost_name, mut text) in ghost_names {
+        if !strings.is_changed() && !ghost_name.is_added() {
+            continue;
+        }
+        text.0 = catalog.display_name(&ghost_name.0, &strings);
+    }
+}
+
 fn load_audio(
     assets: Res<AssetServer>,
     mut audio_handles: ResMut<AudioHandles>,
+    mut sfx_synths: ResMut<Assets<SfxSynth>>,
+    mut sfx_bank: ResMut<SfxBank>,
 ) {
     audio_handles.music = Some(assets.load("audio/dark-cold-beat.downsampled.wav"));
-
-    audio_handles.rectified = Some(assets.load("audio/Rectified.wav"));
-    audio_handles.rectified_inv = Some(assets.load("audio/Rectified_inverted.wav"));
-    audio_handles.sawtooth = Some(assets.load("audio/Sawtooth.wav"));
-    audio_handles.sawtooth_inv = Some(assets.load("audio/Sawtooth_inverted.wav"));
-    audio_handles.sine = Some(assets.load("audio/Sine.wav"));
-    audio_handles.sine_inv = Some(assets.load("audio/Sine_inverted.wav"));
-    audio_handles.square = Some(assets.load("audio/Square.wav"));
-    audio_handles.square_inv = Some(assets.load("audio/Square_inverted.wav"));
-    audio_handles.triangle = Some(assets.load("audio/Triangle.wav"));
-    audio_handles.triangle_inv = Some(assets.load("audio/Triangle_inverted.wav"));
+    for sound in SfxSound::ALL {
+        sfx_bank.sounds.insert(sound, sfx_synths.add(SfxSynth { sound }));
+    }
 }
 
 fn load_sprites(
     assets: Res<AssetServer>,
+    catalog: Res<GhostCatalog>,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<ColorMaterial>>,
     mut sprites: ResMut<Sprites>
 ) {
-    let mut handles = Vec::<[Handle<Image>; 14]>::new();
-    for body in 0..8 {
+    let mut handles = Vec::<Vec<Handle<Image>>>::new();
+    for body in 0..catalog.bodies.len() {
         let mut handles_by_body = Vec::<Handle<Image>>::new();
-        let body_name = GHOST_BODY_NAMES[body];
-        for hat in 0..14 {
-            let hat_name = GHOST_HAT_NAMES[hat];
-            let file_name = format!("ghosts/{body_name}_{hat_name}.png");
-            let handle: Handle<Image> = assets.load(file_name);
+        for hat in 0..catalog.hats.len() {
+            let handle: Handle<Image> = assets.load(catalog.sprite_path(body, hat));
             handles_by_body.push(handle);
         }
-        handles.push(handles_by_body
-            .try_into()
-            .expect("Vec should have 14 elements"));
+        handles.push(handles_by_body);
     }
-    sprites.ghosts = Some(handles.try_into().expect("Vec should have 8 elements"));
+    sprites.ghosts = Some(handles);
+    sprites.bare_ghosts = Some((0..catalog.bodies.len())
+        .map(|body| assets.load(catalog.bare_sprite_path(body)))
+        .collect());
     sprites.background = Some(assets.load("ui/Background.png"));
     sprites.frame = Some(assets.load("ui/Frame.png"));
     sprites.remote_base = Some(assets.load("ui/Machine.png"));
@@ -465,12 +1689,10 @@ fn load_sprites(
 
     sprites.remote_handle = Some(assets.load("ui/Handle.png"));
 
-    let mut counters = Vec::<Handle<Image>>::new();
-    for counter_idx in 0..=10 {
-        let file_name = format!("ui/Counter{}.png", counter_idx);
-        counters.push(assets.load(file_name));
-    }
-    sprites.frame_counter = Some(counters.try_into().expect("Vec should have 10 elements"));
+    sprites.counter_frame_mesh = Some(meshes.add(Rectangle::new(COUNTER_DIGIT_WIDTH, 135.0)));
+    sprites.counter_frame_material = Some(materials.add(Color::srgb(0.42, 0.4, 0.46)));
+    sprites.counter_panel_mesh = Some(meshes.add(Rectangle::new(COUNTER_DIGIT_WIDTH - 12.0, 118.0)));
+    sprites.counter_panel_material = Some(materials.add(Color::srgb(0.6, 0.58, 0.64)));
 
     let mut wave_particles = Vec::<Handle<Image>>::new();
     for wave in GHOST_WAVE_NAMES {
@@ -491,70 +1713,66 @@ fn load_sprites(
     sprites.flash_material = Some(materials.add(Color::WHITE).into());
 }
 
+// Some targets just don't have a remote that can beat them, so they get swapped out until one
+// turns up. The catalog is small enough that this never takes long
+fn choose_targets_and_wave(
+    catalog: &GhostCatalog,
+    capture_zones: &CaptureZones,
+    lanes: &LaneLayout,
+    rng: &mut impl Rng,
+) -> (TargetGhostTags, GhostWaveConfig) {
+    loop {
+        let target_ghosts = choose_target_ghosts(catalog, capture_zones.target_count(), rng);
+        if let Some(ghost_wave) = build_ghost_wave_config(&target_ghosts, capture_zones, lanes, rng) {
+            return (target_ghosts, ghost_wave);
+        }
+    }
+}
+
 fn build_ghost_wave_config(
     target_ghosts: &TargetGhostTags,
-) -> GhostWaveConfig {
-    let mut rng = rand::rng();
-    let mut other_tags: Vec<i8> = target_ghosts.other_tags.iter().filter_map(|x| *x).collect();
-    other_tags.shuffle(&mut rng);
-    //TODO: this only works as hardcoded because we "know" there are exactly 4 other tags
-    let alt_1 = other_tags.pop().unwrap();
-    let alt_2 = other_tags.pop().unwrap();
-    let alt_3 = other_tags.pop().unwrap();
-    let alt_4 = other_tags.pop().unwrap();
-    let button_1 = [
-        Some(target_ghosts.target.body_tag),
-        Some(alt_1),
-        None,
-        None,
-    ];
-    let button_2 = [
-        Some(target_ghosts.target.body_tag),
-        Some(alt_2),
-        None,
-        None,
-    ];
-    let button_3 = [
-        Some(alt_3),
-        None,
-        None,
-        None,
-    ];
-    let button_4 = [
-        Some(alt_4),
-        None,
-        None,
-        None,
-    ];
-    let button_5 = [
-        Some(target_ghosts.target.hat_tag),
-        None,
-        None,
-        None,
-    ];
-    let mut buttons = vec![
-        button_1,
-        button_2,
-        button_3,
-        button_4,
-        button_5,
-    ];
-    buttons.shuffle(&mut rng);
+    capture_zones: &CaptureZones,
+    lanes: &LaneLayout,
+    rng: &mut impl Rng,
+) -> Option<GhostWaveConfig> {
+    for _ in 0..GHOST_WAVE_MAX_ATTEMPTS {
+        let ghost_wave = roll_ghost_wave_config(target_ghosts, rng);
+        if ghost_wave_is_solvable(&ghost_wave, target_ghosts, capture_zones, lanes) {
+            return Some(ghost_wave);
+        }
+    }
+    return None;
+}
 
+fn roll_ghost_wave_config(
+    target_ghosts: &TargetGhostTags,
+    rng: &mut impl Rng,
+) -> GhostWaveConfig {
+    let all_tags = &target_ghosts.all_tags;
+    let mut tag_pool = Vec::<GhostTag>::new();
+    let mut spare_tags = Vec::<GhostTag>::new();
+    let mut buttons = Vec::<ButtonConfig>::new();
+    for _ in 0..5 {
+        // make sure there's always a full set of tags left to draw from
+        if tag_pool.len() < all_tags.len() {
+            let mut deck = all_tags.to_vec();
+            deck.shuffle(rng);
+            tag_pool.splice(0..0, deck);
+        }
+        let tag_count = *[1, 1, 2, 2, 3, 4].choose(rng).unwrap();
+        let mut interactions = [None; 4];
+        select_button_interactions(tag_count, &mut tag_pool, &mut spare_tags, &mut interactions);
+        buttons.push(build_button_config(interactions, rng));
+    }
+    let Ok(buttons) = buttons.try_into() else { panic!("Vec should have 5 elements") };
     return GhostWaveConfig {
-        buttons: [
-            build_button_config(buttons.pop().unwrap(), &mut rng),
-            build_button_config(buttons.pop().unwrap(), &mut rng),
-            build_button_config(buttons.pop().unwrap(), &mut rng),
-            build_button_config(buttons.pop().unwrap(), &mut rng),
-            build_button_config(buttons.pop().unwrap(), &mut rng),
-        ],
+        buttons,
         dial_strength: 1,
     };
 }
 
-fn build_button_config(tags: [Option<i8>; 4], rng: &mut ThreadRng) -> ButtonConfig {
-    let strength = if (0..=1).choose(rng).unwrap() == 0 { -1i8 } else { 1i8 };
+fn build_button_config(tags: [Option<GhostTag>; 4], rng: &mut impl Rng) -> ButtonConfig {
+    let strength = *[-2i8, -1, -1, 1, 1, 2].choose(rng).unwrap();
     return ButtonConfig {
         interactions: tags.clone(),
         strength,
@@ -563,14 +1781,14 @@ fn build_button_config(tags: [Option<i8>; 4], rng: &mut ThreadRng) -> ButtonConf
     }
 }
 
-fn select_button_interactions(n: usize, tag_pool: &mut Vec<i8>, spare_tags: &mut Vec<i8>, button_array: &mut [i8; 4]) {
+fn select_button_interactions(n: usize, tag_pool: &mut Vec<GhostTag>, spare_tags: &mut Vec<GhostTag>, button_array: &mut [Option<GhostTag>; 4]) {
     for i in 0..n {
         loop {
             let tag = tag_pool.pop().unwrap();
-            if button_array.contains(&tag) {
+            if button_array.contains(&Some(tag)) {
                 spare_tags.push(tag);
             } else {
-                button_array[i] = tag;
+                button_array[i] = Some(tag);
                 break;
             }
         }
@@ -580,71 +1798,219 @@ fn select_button_interactions(n: usize, tag_pool: &mut Vec<i8>, spare_tags: &mut
     }
 }
 
+// Tries every way the buttons can be set to find how far each target can be stepped, and how
+// far every other kind of ghost can be moved without also moving that target. Then walks those
+// moves through the lanes, walls and all, to check each target can reach its capture lane and
+// nothing else gets stuck in one. Jitter only adds drift on top, and twins share their tags so
+// they move like the ghost they split from
+fn ghost_wave_is_solvable(
+    ghost_wave: &GhostWaveConfig,
+    target_ghosts: &TargetGhostTags,
+    capture_zones: &CaptureZones,
+    lanes: &LaneLayout,
+) -> bool {
+    let mut buttons = ghost_wave.buttons.clone();
+    let all_ghosts: Vec<(GhostTags, Option<GhostBehaviour>)> = target_ghosts.targets.iter()
+        .map(|target| (*target, None))
+        .chain(target_ghosts.others.iter().copied().zip(target_ghosts.other_behaviours.iter().copied()))
+        .collect();
+    let mut target_steps = vec![Vec::<i8>::new(); target_ghosts.targets.len()];
+    // by target, then by ghost
+    let mut ghost_moves_alone = vec![vec![Vec::<i8>::new(); all_ghosts.len()]; target_ghosts.targets.len()];
+    // each button is either off, on, or on and inverted
+    for setting in 0..3usize.pow(buttons.len() as u32) {
+        for (i, button) in buttons.iter_mut().enumerate() {
+            let button_state = setting / 3usize.pow(i as u32) % 3;
+            button.enabled = button_state != 0;
+            button.inverted = button_state == 2;
+        }
+        let mut tag_moves = HashMap::<GhostTag, i8>::new();
+        for button in buttons.iter() {
+            add_to_tag_moves(&mut tag_moves, button);
+        }
+        for (target_idx, target) in target_ghosts.targets.iter().enumerate() {
+            // turning the dial only multiplies these, so there's nothing new to find past 2,
+            // which is as far as it takes to budge a stubborn ghost
+            let target_move = get_ghost_lane_change(&tag_moves, target, 1);
+            if target_move.abs() == 1 && !target_steps[target_idx].contains(&target_move) {
+                target_steps[target_idx].push(target_move);
+            }
+            if target_move != 0 {
+                continue;
+            }
+            for (ghost_idx, (ghost, behaviour)) in all_ghosts.iter().enumerate() {
+                for wave_strength in 1..=2 {
+                    let move_acc = get_ghost_lane_change(&tag_moves, ghost, wave_strength);
+                    let move_acc = apply_ghost_behaviour(behaviour.as_ref(), move_acc, wave_strength);
+                    if move_acc != 0 && !ghost_moves_alone[target_idx][ghost_idx].contains(&move_acc) {
+                        ghost_moves_alone[target_idx][ghost_idx].push(move_acc);
+                    }
+                }
+            }
+        }
+    }
+    let capture_lanes: Vec<i8> = capture_zones.zones.iter().map(|zone| zone.lane as i8).collect();
+    let spawn_lanes = LANE_LAYOUT_BUFFER_LANES..LANE_LAYOUT_BUFFER_LANES + LANE_LAYOUT_SPAWN_LANES;
+    for zone in capture_zones.zones.iter() {
+        for &target_idx in zone.targets.iter() {
+            for spawn_lane in spawn_lanes.clone() {
+                let reachable = get_reachable_lanes(lanes, spawn_lane, &target_steps[target_idx]);
+                if !reachable.contains(&(zone.lane as i8)) {
+                    return false;
+                }
+            }
+        }
+    }
+    for (target_idx, target) in target_ghosts.targets.iter().enumerate() {
+        for (ghost_idx, (ghost, _)) in all_ghosts.iter().enumerate() {
+            if ghost == target {
+                continue;
+            }
+            // wherever it ends up in a capture lane, it has to be able to get back out
+            for &capture_lane in capture_lanes.iter() {
+                let reachable = get_reachable_lanes(lanes, capture_lane as u8, &ghost_moves_alone[target_idx][ghost_idx]);
+                if reachable.iter().all(|lane| capture_lanes.contains(lane)) {
+                    return false;
+                }
+            }
+        }
+    }
+    return true;
+}
+
+// Every lane a ghost can end up in from `from` by repeating the given moves in any order, going
+// off either edge counts as a lane too but doesn't lead anywhere else
+fn get_reachable_lanes(lanes: &LaneLayout, from: u8, moves: &[i8]) -> Vec<i8> {
+    let mut reachable = vec![from as i8];
+    let mut to_visit = vec![from];
+    while let Some(lane) = to_visit.pop() {
+        for &move_acc in moves {
+            let next_lane = lanes.resolve_lane_move(lane, move_acc);
+            if reachable.contains(&next_lane) {
+                continue;
+            }
+            reachable.push(next_lane);
+            if next_lane >= 0 && next_lane < LANE_LAYOUT_LANE_COUNT as i8 {
+                to_visit.push(next_lane as u8);
+            }
+        }
+    }
+    return reachable;
+}
+
 //TODO: randomly generate this instead based on current level
-fn choose_target_ghosts() -> TargetGhostTags {
-    let mut rng = rand::rng();
-    let mut hats = (TAG_HAT_1..=TAG_HAT_14).collect::<Vec<i8>>();
-    hats.shuffle(&mut rng);
-    let target_hat = hats.pop().unwrap();
-    let variant_hat_1 = hats.pop().unwrap();
-    let variant_hat_2 = hats.pop().unwrap();
-
-    let mut bodies = (TAG_BODY_1..=TAG_BODY_8).collect::<Vec<i8>>();
-    bodies.shuffle(&mut rng);
-    let target_body = bodies.pop().unwrap();
-    let variant_body_1 = bodies.pop().unwrap();
-    let variant_body_2 = bodies.pop().unwrap();
-
-    let variant_1 = GhostTags::new(variant_body_1, target_hat);
-    let variant_2 = GhostTags::new(target_body, variant_hat_1);
-    let variant_3 = GhostTags::new(variant_body_2, variant_hat_1);
-    let variant_4 = GhostTags::new(variant_body_1, variant_hat_2);
+fn choose_target_ghosts(catalog: &GhostCatalog, target_count: usize, rng: &mut impl Rng) -> TargetGhostTags {
+    let mut hats = catalog.hat_tags().collect::<Vec<GhostTag>>();
+    hats.shuffle(rng);
+    let mut bodies = catalog.body_tags().collect::<Vec<GhostTag>>();
+    bodies.shuffle(rng);
+    let mut colors = catalog.color_tags().collect::<Vec<GhostTag>>();
+    colors.shuffle(rng);
+    let target_color = colors.pop().unwrap();
+    let variant_color = colors.pop().unwrap();
+
+    let mut targets = Vec::<GhostTags>::new();
+    let mut others = Vec::<GhostTags>::new();
+    let mut all_tags = vec![target_color, variant_color];
+    for target_idx in 0..target_count {
+        let mut random_color = || *[target_color, variant_color].choose(rng).unwrap();
+        let target_hat = hats.pop().unwrap();
+        let variant_hat_1 = hats.pop().unwrap();
+        let target_body = bodies.pop().unwrap();
+        let variant_body_1 = bodies.pop().unwrap();
+        let target = GhostTags::new(target_body, target_hat, target_color);
+        targets.push(target);
+        others.push(GhostTags::new(variant_body_1, target_hat, random_color()));
+        others.push(GhostTags::new(target_body, variant_hat_1, random_color()));
+        all_tags.extend([target_body, target_hat, variant_body_1, variant_hat_1]);
+        if target_idx == 0 {
+            // the first target gets the full set of lookalikes, the rest just get the one for each
+            // of their tags so there's room for everything on the field
+            let variant_body_2 = bodies.pop().unwrap();
+            let variant_hat_2 = hats.pop().unwrap();
+            others.push(GhostTags::new(variant_body_2, variant_hat_1, random_color()));
+            others.push(GhostTags::new(variant_body_1, variant_hat_2, random_color()));
+            // looks just like the target apart from its color
+            others.push(GhostTags::new(target_body, target_hat, variant_color));
+            all_tags.extend([variant_body_2, variant_hat_2]);
+        }
+    }
+
+    let mut random_behaviour = || {
+        if !rng.random_bool(GHOST_BEHAVIOUR_CHANCE) {
+            return None;
+        }
+        let behaviours = [
+            GhostBehaviour::Stubborn,
+            GhostBehaviour::Jittery { every: rng.random_range(2..=3), firings: 0 },
+            GhostBehaviour::Mirror,
+            GhostBehaviour::Twin,
+        ];
+        return behaviours.choose(rng).copied();
+    };
+    let other_behaviours = others.iter().map(|_| random_behaviour()).collect();
+
+    // every extra target is worth its own amount of reputation
+    let rewards = (0..targets.len())
+        .map(|target_idx| if target_idx == 0 { 1 } else { rng.random_range(1..=2) })
+        .collect();
 
     return TargetGhostTags {
-        target: GhostTags {
-            body_tag: target_body,
-            hat_tag: target_hat,
-        },
-        others: [
-            Some(variant_1),
-            Some(variant_2),
-            Some(variant_3),
-            Some(variant_4),
-            None,
-            None,
-            None,
-            None,
-        ],
-        all_tags: [
-            Some(target_body),
-            Some(target_hat),
-            Some(variant_body_1),
-            Some(variant_body_2),
-            Some(variant_hat_1),
-            Some(variant_hat_2),
-            None,
-            None,
-        ],
-        other_tags: [
-            Some(variant_body_1),
-            Some(variant_body_2),
-            Some(variant_hat_1),
-            Some(variant_hat_2),
-            None,
-            None,
-            None,
-            None,
-        ],
+        targets,
+        rewards,
+        others,
+        other_behaviours,
+        all_tags,
     };
 }
 
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum LaneProperty {
+    Normal,
+    // ghosts can't enter, so anything heading this way stops at the edge
+    Blocked,
+    // ghosts leaving this lane only go half as far
+    Slow,
+    // ghosts landing here are sent on to the paired lane
+    Portal(u8),
+}
+
 #[derive(Resource)]
 struct LaneLayout {
     //lanes: Vec<Rect>,
     margined_lanes: Vec<Rect>,
+    properties: Vec<LaneProperty>,
+}
+impl LaneLayout {
+    // Works out which lane a ghost ends up in after being pushed `move_acc` lanes. Anything
+    // outside of the lanes means the ghost has wandered off.
+    fn resolve_lane_move(&self, from: u8, move_acc: i8) -> i8 {
+        let mut move_acc = move_acc;
+        if self.properties[from as usize] == LaneProperty::Slow {
+            // round away from zero so a nudge still does something
+            move_acc = (move_acc + move_acc.signum()) / 2;
+        }
+        let step = move_acc.signum();
+        let mut lane = from as i8;
+        for _ in 0..move_acc.abs() {
+            let next_lane = lane + step;
+            if next_lane < 0 || next_lane >= self.properties.len() as i8 {
+                return next_lane;
+            }
+            if self.properties[next_lane as usize] == LaneProperty::Blocked {
+                break;
+            }
+            lane = next_lane;
+        }
+        if let LaneProperty::Portal(paired_lane) = self.properties[lane as usize]
+            && lane != from as i8 {
+            lane = paired_lane as i8;
+        }
+        return lane;
+    }
 }
 
-fn build_lane_layout() -> LaneLayout {
+fn build_lane_layout(capture_zones: &CaptureZones, rng: &mut impl Rng) -> LaneLayout {
     //let mut lanes = Vec::<Rect>::new();
     let mut margined_lanes = Vec::<Rect>::new();
     for lane in 0..LANE_LAYOUT_LANE_COUNT {
@@ -654,124 +2020,605 @@ fn build_lane_layout() -> LaneLayout {
     return LaneLayout {
         //lanes,
         margined_lanes,
+        properties: roll_lane_properties(capture_zones, rng),
     };
 }
 
-fn get_lane_boundary(lane: u8, margin: f32) -> Rect {
-    let left = LANE_LAYOUT_LEFT + (lane as f32 * LANE_LAYOUT_LANE_WIDTH) + margin;
-    let right = left + LANE_LAYOUT_LANE_WIDTH - margin - margin;
-    let top = LANE_LAYOUT_BOTTOM + LANE_LAYOUT_HEIGHT - margin;
-    let bottom = LANE_LAYOUT_BOTTOM + margin;
-    return Rect {
-        min: Vec2::new(left, bottom),
-        max: Vec2::new(right, top),
-    };
-}
+fn roll_lane_properties(capture_zones: &CaptureZones, rng: &mut impl Rng) -> Vec<LaneProperty> {
+    let mut properties = vec![LaneProperty::Normal; LANE_LAYOUT_LANE_COUNT as usize];
+    // Only the innermost buffer lanes get blocked off. Anywhere else and some of the ghosts
+    // could be walled away from the capture lane for good
+    let left_buffer = LANE_LAYOUT_BUFFER_LANES - 1;
+    let right_buffer = LANE_LAYOUT_LANE_COUNT - LANE_LAYOUT_BUFFER_LANES;
+    for buffer_lane in [left_buffer, right_buffer] {
+        if rng.random_bool(LANE_HAZARD_CHANCE) {
+            properties[buffer_lane as usize] = LaneProperty::Blocked;
+        }
+    }
 
-fn get_random_point_in_rect(rect: &Rect) -> Vec2 {
-    let width = rect.max.x - rect.min.x;
-    let height = rect.max.y - rect.min.y;
-    let x = rect.min.x + rand::random::<f32>() * width;
-    let y = rect.min.y + rand::random::<f32>() * height;
-    return Vec2::new(x, y);
+    // keep the capture lanes clear so it's always obvious what's about to be captured
+    let spawn_lanes: Vec<u8> = (LANE_LAYOUT_BUFFER_LANES..LANE_LAYOUT_BUFFER_LANES + LANE_LAYOUT_SPAWN_LANES)
+        .filter(|&x| !capture_zones.zones.iter().any(|zone| zone.lane == x))
+        .collect();
+    let left_lanes: Vec<u8> = spawn_lanes.iter().copied().filter(|&x| x < LANE_LAYOUT_CENTER_LANE).collect();
+    let right_lanes: Vec<u8> = spawn_lanes.iter().copied().filter(|&x| x > LANE_LAYOUT_CENTER_LANE).collect();
+    if rng.random_bool(LANE_HAZARD_CHANCE) && !left_lanes.is_empty() && !right_lanes.is_empty() {
+        let left = *left_lanes.choose(rng).unwrap();
+        let right = *right_lanes.choose(rng).unwrap();
+        properties[left as usize] = LaneProperty::Portal(right);
+        properties[right as usize] = LaneProperty::Portal(left);
+    }
+    if rng.random_bool(LANE_HAZARD_CHANCE) {
+        let slow_lane = spawn_lanes.iter()
+            .copied()
+            .filter(|&x| properties[x as usize] == LaneProperty::Normal)
+            .choose(rng);
+        if let Some(slow_lane) = slow_lane {
+            properties[slow_lane as usize] = LaneProperty::Slow;
+        }
+    }
+    return properties;
 }
 
 #[derive(Component)]
-struct Ghost;
-
-#[derive(Component)]
-struct GhostAnimationLoop {
-    base_scale: f32,
-    theta_x: f32,
-    omega_x: f32,
-    radius_x: f32,
+struct LaneOverlay;
 
-    theta_y: f32,
-    omega_y: f32,
-    radius_y: f32,
-    offset_y: f32,
+fn spawn_lane_overlays(
+    lanes: Res<LaneLayout>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut commands: Commands,
+) {
+    let lane_mesh = meshes.add(Rectangle::new(LANE_LAYOUT_LANE_WIDTH, LANE_LAYOUT_HEIGHT));
+    for (lane, property) in lanes.properties.iter().enumerate() {
+        let color = match property {
+            LaneProperty::Normal => continue,
+            LaneProperty::Blocked => Color::srgba(0.1, 0.0, 0.05, 0.55),
+            LaneProperty::Slow => Color::srgba(0.35, 0.55, 1.0, 0.25),
+            LaneProperty::Portal(_) => Color::srgba(0.7, 0.3, 1.0, 0.3),
+        };
+        let center = get_lane_boundary(lane as u8, 0.0).center();
+        commands.spawn((
+            LaneOverlay,
+            Mesh2d(lane_mesh.clone()),
+            MeshMaterial2d(materials.add(color)),
+            Transform::from_xyz(center.x, center.y, Z_POS_LANE_OVERLAY),
+        ));
+    }
 }
 
-#[derive(Component)]
-struct GhostLanePosition {
-    lane: u8
+struct CaptureZone {
+    lane: u8,
+    // indices into `TargetGhostTags::targets`
+    targets: Vec<usize>,
 }
 
-#[derive(Component)]
-struct GhostScooting {
-    scoot_target: Vec2,
-    movement_speed: f32,
+// The lanes the capture buttons pull ghosts out of this round, and which targets belong in each
+#[derive(Resource)]
+struct CaptureZones {
+    zones: Vec<CaptureZone>,
 }
-
-#[derive(PartialEq, Eq)]
-enum ClickableType {
-    Dial,
-    WaveEnable(i8),
-    WaveInvert(i8),
-    FireWave,
-    CaptureGhosts,
+impl CaptureZones {
+    fn target_count(&self) -> usize {
+        self.zones.iter().map(|zone| zone.targets.len()).sum()
+    }
 }
 
-#[derive(Message)]
-struct CaptureGhostsInitialized;
+fn choose_capture_zones(catalog: &GhostCatalog, rng: &mut impl Rng) -> CaptureZones {
+    if let Ok(layout) = catalog.capture_zone_layouts.choose_weighted(rng, |layout| layout.weight) {
+        let mut next_target = 0;
+        let zones = layout.zones.iter()
+            .map(|zone| {
+                let targets = (next_target..next_target + zone.targets).collect();
+                next_target += zone.targets;
+                return CaptureZone {
+                    lane: (LANE_LAYOUT_CENTER_LANE as i8 + zone.lane_offset) as u8,
+                    targets,
+                };
+            })
+            .collect();
+        return CaptureZones {
+            zones,
+        };
+    }
+    let zones = if rng.random_bool(CAPTURE_ZONE_SPLIT_CHANCE) {
+        vec![
+            CaptureZone { lane: LANE_LAYOUT_CENTER_LANE - 1, targets: vec![0] },
+            CaptureZone { lane: LANE_LAYOUT_CENTER_LANE + 1, targets: vec![1] },
+        ]
+    } else {
+        let target_count = *[1, 1, 1, 2, 2, 3].choose(rng).unwrap();
+        vec![
+            CaptureZone { lane: LANE_LAYOUT_CENTER_LANE, targets: (0..target_count).collect() },
+        ]
+    };
+    return CaptureZones {
+        zones,
+    };
+}
 
 #[derive(Component)]
-struct Clickable {
-    clickable_type: ClickableType,
-    bounds: Rect,
-}
+struct CaptureZoneMarker;
 
-fn spawn_ui(
+fn spawn_capture_zones(
     sprites: Res<Sprites>,
-    target_ghost: Res<TargetGhostTags>,
-    player_resources: Res<PlayerResources>,
+    catalog: Res<GhostCatalog>,
+    capture_zones: Res<CaptureZones>,
+    target_ghosts: Res<TargetGhostTags>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
     mut commands: Commands,
 ) {
-    let ghost_sprites = sprites.ghosts.as_ref().expect("Sprites should be loaded");
-    let background = sprites.background.clone().expect("Sprites should be loaded");
-    let frame = sprites.frame.clone().expect("Sprites should be loaded");
-    let remote_base = sprites.remote_base.clone().expect("Sprites should be loaded");
-    let dial = sprites.remote_dial.as_ref().expect("Sprites should be loaded")[0].clone();
-    let handle = sprites.remote_handle.clone().expect("Sprites should be loaded");
-    let waves = sprites.remote_wave_buttons.as_ref().expect("Sprites should be loaded");
-    let toggles = sprites.remote_wave_inverter.as_ref().expect("Sprites should be loaded");
-    let lights = sprites.remote_wave_light.as_ref().expect("Sprites should be loaded");
-    let counters = sprites.frame_counter.as_ref().expect("Sprites should be loaded");
-    let body_idx = (target_ghost.target.body_tag - TAG_BODY_1) as usize;
-    let hat_idx = target_ghost.target.hat_tag as usize;
-    let target_ghost_sprite = ghost_sprites[body_idx][hat_idx].clone();
-    commands.spawn((
-        Sprite::from_image(background),
-        Transform::from_xyz(0.0, 0.0, Z_POS_BACKGROUND)
-    ));
-    commands.spawn((
-        Sprite::from_image(frame),
-        Transform::from_xyz(0.0, 0.0, Z_POS_FRAME)
-    ))
-    .with_children(|cmd| {
-        cmd.spawn((
-            Transform::from_xyz(-345.0, 1075.0, 0.0),
+    let lane_mesh = meshes.add(Rectangle::new(LANE_LAYOUT_LANE_WIDTH, LANE_LAYOUT_HEIGHT));
+    let arrow_mesh = meshes.add(Triangle2d::new(
+        Vec2::new(-50.0, 40.0),
+        Vec2::new(50.0, 40.0),
+        Vec2::new(0.0, -40.0)));
+    let button_mesh = meshes.add(Rectangle::new(440.0, 150.0));
+    let button_color = materials.add(Color::srgb(0.396, 0.0, 0.255));
+    for (zone_idx, zone) in capture_zones.zones.iter().enumerate() {
+        let zone_color = CAPTURE_ZONE_COLORS[zone_idx];
+        let arrow_color = materials.add(zone_color);
+        let lane_center = get_lane_boundary(zone.lane, 0.0).center();
+        commands.spawn((
+            CaptureZoneMarker,
+            Mesh2d(lane_mesh.clone()),
+            MeshMaterial2d(materials.add(zone_color.with_alpha(0.12))),
+            Transform::from_xyz(lane_center.x, lane_center.y, Z_POS_LANE_OVERLAY),
+        ));
+        commands.spawn((
+            CaptureZoneMarker,
+            Mesh2d(arrow_mesh.clone()),
+            MeshMaterial2d(arrow_color.clone()),
+            Transform::from_xyz(lane_center.x, LANE_LAYOUT_BOTTOM + LANE_LAYOUT_HEIGHT + 60.0, Z_POS_FRAME + 1.0),
+        ));
+
+        let button_pos = CAPTURE_BUTTON_POSITIONS[zone_idx];
+        commands.spawn((
+            CaptureZoneMarker,
+            Transform::from_xyz(button_pos.x, button_pos.y, Z_POS_FRAME + 1.0),
+            Visibility::Visible,
             Clickable {
-                clickable_type: ClickableType::CaptureGhosts,
+                clickable_type: ClickableType::CaptureGhosts(zone_idx),
                 bounds: Rect::new(-220.0, -75.0, 220.0, 75.0),
             },
-        ));
-        cmd.spawn((
+        )).with_children(|cmd| {
+            // the first zone's button is already drawn on the frame
+            if zone_idx > 0 {
+                cmd.spawn((
+                    Mesh2d(button_mesh.clone()),
+                    MeshMaterial2d(button_color.clone()),
+                    Transform::from_xyz(0.0, 0.0, -0.5),
+                ));
+            }
+            cmd.spawn((
+                Mesh2d(arrow_mesh.clone()),
+                MeshMaterial2d(arrow_color.clone()),
+                Transform::from_xyz(-280.0, 0.0, 0.0)
+                    .with_scale(Vec3::new(0.6, 0.6, 1.0)),
+            ));
+            for (i, &target_idx) in zone.targets.iter().enumerate() {
+                let mut zone_target = sprites.ghost(&catalog, &target_ghosts.targets[target_idx]);
+                zone_target.custom_size = Some(Vec2::new(120.0, 120.0));
+                cmd.spawn((
+                    zone_target,
+                    GhostTint(target_ghosts.targets[target_idx].color_tag),
+                    Transform::from_xyz(280.0 + 130.0 * i as f32, 0.0, 0.0),
+                ));
+            }
+        });
+    }
+}
+
+fn spawn_target_displays(
+    sprites: Res<Sprites>,
+    catalog: Res<GhostCatalog>,
+    target_ghosts: Res<TargetGhostTags>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut commands: Commands,
+) {
+    let star_sprite = sprites.ghost_particles.as_ref().expect("Sprites should be loaded");
+    let tick_short = meshes.add(Rectangle::new(90.0, 30.0));
+    let tick_long = meshes.add(Rectangle::new(180.0, 30.0));
+    let tick_color = materials.add(Color::srgb(0.2, 0.85, 0.3));
+    // shrink them down a bit to make room when there's more than one
+    let display_scale = if target_ghosts.targets.len() == 1 { 0.5 } else { 0.38 };
+    for (target_idx, target) in target_ghosts.targets.iter().enumerate() {
+        commands.spawn((
             TargetGhostDisplay,
-            Sprite::from_image(target_ghost_sprite),
-            Transform::from_xyz(-1580.0, 860.0, 1.0)
-                .with_scale(Vec3::new(0.5, 0.5, 1.0))
+            sprites.ghost(&catalog, target),
+            GhostTint(target.color_tag),
+            Transform::from_xyz(-1580.0 + 300.0 * target_idx as f32, 860.0, Z_POS_FRAME + 1.0)
+                .with_scale(Vec3::new(display_scale, display_scale, 1.0))
                 .with_rotation(Quat::from_euler(EulerRot::XYZ, 0.0, 0.0, std::f32::consts::PI / 12.0)),
-        ));
+        )).with_children(|cmd| {
+            cmd.spawn((
+                TargetClearedTick {
+                    target_idx,
+                },
+                Visibility::Hidden,
+                Transform::from_xyz(160.0, -160.0, 1.0),
+            )).with_children(|cmd| {
+                cmd.spawn((
+                    Mesh2d(tick_short.clone()),
+                    MeshMaterial2d(tick_color.clone()),
+                    Transform::from_xyz(-55.0, -10.0, 0.0)
+                        .with_rotation(Quat::from_rotation_z(-std::f32::consts::PI / 4.0)),
+                ));
+                cmd.spawn((
+                    Mesh2d(tick_long.clone()),
+                    MeshMaterial2d(tick_color.clone()),
+                    Transform::from_xyz(20.0, 15.0, 0.0)
+                        .with_rotation(Quat::from_rotation_z(std::f32::consts::PI / 4.0)),
+                ));
+            });
+            // one star for each point of reputation it's worth
+            let reward = target_ghosts.rewards[target_idx];
+            for i in 0..reward {
+                let star_x = (i as f32 - (reward - 1) as f32 / 2.0) * 120.0;
+                cmd.spawn((
+                    Sprite::from_image(star_sprite.clone()),
+                    Transform::from_xyz(star_x, -300.0, 1.0),
+                ));
+            }
+        });
+    }
+}
+
+#[derive(Component)]
+struct LegendPanel;
+
+#[derive(Component)]
+struct NotebookCell {
+    entry: usize,
+    button: usize,
+}
+
+// The target comes first, then every lookalike, in the same order as the notebook rows
+fn get_legend_entries(target_ghosts: &TargetGhostTags) -> Vec<(GhostTags, Option<GhostBehaviour>)> {
+    return target_ghosts.targets.iter()
+        .map(|target| (*target, None))
+        .chain(target_ghosts.others.iter().copied().zip(target_ghosts.other_behaviours.iter().copied()))
+        .collect();
+}
+
+fn spawn_legend_panel(
+    sprites: Res<Sprites>,
+    catalog: Res<GhostCatalog>,
+    target_ghosts: Res<TargetGhostTags>,
+    mut notebook: ResMut<DeductionNotebook>,
+    mut commands: Commands,
+) {
+    const ROW_HEIGHT: f32 = 110.0;
+    const COLUMN_X_START: f32 = 150.0;
+    const COLUMN_WIDTH: f32 = 150.0;
+    let wave_sprites = sprites.wave_particles.as_ref().expect("Sprites should be loaded");
+    let entries = get_legend_entries(&target_ghosts);
+    notebook.observations = vec![[None; 5]; entries.len()];
+    let rows_top = ROW_HEIGHT * entries.len() as f32 / 2.0;
+    let visibility = if notebook.open { Visibility::Visible } else { Visibility::Hidden };
+    commands.spawn((
+        LegendPanel,
+        Sprite::from_color(
+            Color::srgba(0.05, 0.05, 0.1, 0.9),
+            Vec2::new(1900.0, rows_top * 2.0 + 2.0 * ROW_HEIGHT)),
+        Transform::from_xyz(-355.0, -100.0, Z_POS_LEGEND),
+        visibility,
+    )).with_children(|cmd| {
+        for (button, wave_sprite) in wave_sprites.iter().enumerate() {
+            cmd.spawn((
+                Sprite::from_image(wave_sprite.clone()),
+                Transform::from_xyz(COLUMN_X_START + COLUMN_WIDTH * button as f32, rows_top + ROW_HEIGHT / 2.0, 1.0)
+                    .with_scale(Vec3::new(0.6, 0.6, 1.0)),
+            ));
+        }
+        for (entry, (ghost, behaviour)) in entries.iter().enumerate() {
+            let row_y = rows_top - ROW_HEIGHT * (entry as f32 + 0.5);
+            let mut sprite = sprites.ghost(&catalog, ghost);
+            sprite.flip_x = *behaviour == Some(GhostBehaviour::Mirror);
+            cmd.spawn((
+                sprite,
+                GhostTint(ghost.color_tag),
+                Transform::from_xyz(-850.0, row_y, 1.0)
+                    .with_scale(Vec3::new(0.2, 0.2, 1.0)),
+            ));
+            let name_color = if target_ghosts.is_target(ghost) {
+                Color::srgb(1.0, 0.8, 0.2)
+            } else {
+                Color::WHITE
+            };
+            cmd.spawn((
+                Text2d::default(),
+                LocalizedGhostName(*ghost),
+                TextFont::from_font_size(45.0),
+                TextColor(name_color),
+                Transform::from_xyz(-430.0, row_y, 1.0),
+            ));
+            for button in 0..5 {
+                cmd.spawn((
+                    NotebookCell { entry, button },
+                    Text2d::new("?"),
+                    TextFont::from_font_size(50.0),
+                    Transform::from_xyz(COLUMN_X_START + COLUMN_WIDTH * button as f32, row_y, 1.0),
+                ));
+            }
+        }
+    });
+}
+
+fn toggle_legend_panel(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut notebook: ResMut<DeductionNotebook>,
+    panels: Query<&mut Visibility, With<LegendPanel>>,
+) {
+    if !keys.just_pressed(KeyCode::KeyL) {
+        return;
+    }
+    notebook.open = !notebook.open;
+    for mut visibility in panels {
+        *visibility = if notebook.open { Visibility::Visible } else { Visibility::Hidden };
+    }
+}
+
+// Compares where each ghost sits after a firing with where it was before, so the notebook only
+// ever holds what the player could have seen for themselves
+fn record_notebook_observations(
+    mut on_fire: MessageReader<RemoteFired>,
+    ghost_wave: Res<GhostWaveConfig>,
+    target_ghosts: Res<TargetGhostTags>,
+    ghosts: Query<(Entity, &GhostTags, &GhostLanePosition), With<Ghost>>,
+    mut last_lanes: Local<HashMap<Entity, u8>>,
+    mut notebook: ResMut<DeductionNotebook>,
+) {
+    let fired = !on_fire.is_empty();
+    on_fire.clear();
+    let enabled_buttons: Vec<usize> = (0..5)
+        .filter(|&button| ghost_wave.buttons[button].enabled)
+        .collect();
+    if let (&[button], true) = (enabled_buttons.as_slice(), fired) {
+        let wave_strength = ghost_wave.dial_strength as i8;
+        // write it down as if it had been fired upright at strength 1 so it lines up with other notes
+        let normalize = if ghost_wave.buttons[button].inverted { -wave_strength } else { wave_strength };
+        for (entry, (legend_ghost, _)) in get_legend_entries(&target_ghosts).iter().enumerate() {
+            let moves: Vec<i8> = ghosts.iter()
+                .filter(|(_, ghost_tags, _)| *ghost_tags == legend_ghost)
+                .filter_map(|(entity, _, ghost_lane)| {
+                    let last_lane = last_lanes.get(&entity)?;
+                    return Some(ghost_lane.lane as i8 - *last_lane as i8);
+                })
+                .collect();
+            // nothing to go on if none of them were out there, or if they didn't all move the
+            // same way because some got blocked or drifted
+            let Some(&lanes_moved) = moves.first() else { continue };
+            if moves.iter().any(|&other| other != lanes_moved) || lanes_moved % normalize != 0 {
+                continue;
+            }
+            notebook.observations[entry][button] = Some(lanes_moved / normalize);
+        }
+    }
+    last_lanes.clear();
+    for (entity, _, ghost_lane) in ghosts {
+        last_lanes.insert(entity, ghost_lane.lane);
+    }
+}
+
+fn update_notebook_cells(
+    notebook: Res<DeductionNotebook>,
+    cells: Query<(&NotebookCell, &mut Text2d)>,
+) {
+    if !notebook.is_changed() {
+        return;
+    }
+    for (cell, mut text) in cells {
+        let observation = notebook.observations.get(cell.entry).and_then(|row| row[cell.button]);
+        text.0 = match observation {
+            None => "?".to_string(),
+            Some(0) => "0".to_string(),
+            Some(lanes) => format!("{lanes:+}"),
+        };
+    }
+}
+
+fn update_target_ticks(
+    target_ghosts: Res<TargetGhostTags>,
+    ghosts: Query<&GhostTags, With<Ghost>>,
+    ticks: Query<(&mut Visibility, &TargetClearedTick)>,
+) {
+    for (mut visibility, tick) in ticks {
+        let target = target_ghosts.targets[tick.target_idx];
+        let cleared = !ghosts.iter().any(|ghost_tags| *ghost_tags == target);
+        *visibility = if cleared { Visibility::Inherited } else { Visibility::Hidden };
+    }
+}
+
+fn get_lane_boundary(lane: u8, margin: f32) -> Rect {
+    let left = LANE_LAYOUT_LEFT + (lane as f32 * LANE_LAYOUT_LANE_WIDTH) + margin;
+    let right = left + LANE_LAYOUT_LANE_WIDTH - margin - margin;
+    let top = LANE_LAYOUT_BOTTOM + LANE_LAYOUT_HEIGHT - margin;
+    let bottom = LANE_LAYOUT_BOTTOM + margin;
+    return Rect {
+        min: Vec2::new(left, bottom),
+        max: Vec2::new(right, top),
+    };
+}
+
+fn get_random_point_in_rect(rect: &Rect) -> Vec2 {
+    let width = rect.max.x - rect.min.x;
+    let height = rect.max.y - rect.min.y;
+    let x = rect.min.x + rand::random::<f32>() * width;
+    let y = rect.min.y + rand::random::<f32>() * height;
+    return Vec2::new(x, y);
+}
+
+#[derive(Component)]
+struct Ghost;
+
+#[derive(Component, Copy, Clone, PartialEq, Eq, Debug)]
+enum GhostBehaviour {
+    // ignores the remote while the dial is on its lowest setting
+    Stubborn,
+    // drifts a lane in a random direction every few firings
+    Jittery {
+        every: u8,
+        firings: u8,
+    },
+    // moves the opposite way to everyone else
+    Mirror,
+    // leaves a copy of itself behind the first time it's moved
+    Twin,
+}
+
+#[derive(Component)]
+struct TwinEcho;
+
+#[derive(Component)]
+struct GhostAnimationLoop {
+    base_scale: f32,
+    theta_x: f32,
+    omega_x: f32,
+    radius_x: f32,
+
+    theta_y: f32,
+    omega_y: f32,
+    radius_y: f32,
+    offset_y: f32,
+}
+
+#[derive(Component)]
+struct GhostLanePosition {
+    lane: u8
+}
+
+#[derive(Component)]
+struct GhostScooting {
+    scoot_target: Vec2,
+    movement_speed: f32,
+}
+
+#[derive(PartialEq, Eq)]
+enum ClickableType {
+    Dial,
+    WaveEnable(i8),
+    WaveInvert(i8),
+    FireWave,
+    CaptureGhosts(usize),
+    SelectMode(GameMode),
+    EditNotes(i8),
+    PickNote(GhostTag),
+}
+
+#[derive(Message)]
+struct CaptureGhostsInitialized {
+    zone: usize,
+}
+
+#[derive(Component)]
+struct Clickable {
+    clickable_type: ClickableType,
+    bounds: Rect,
+}
+impl Clickable {
+    fn contains(&self, transform: &GlobalTransform, point: Vec2) -> bool {
+        let clickable_pos = transform.translation();
+        let left = clickable_pos.x + self.bounds.min.x;
+        let right = clickable_pos.x + self.bounds.max.x;
+        let bottom = clickable_pos.y + self.bounds.min.y;
+        let top = clickable_pos.y + self.bounds.max.y;
+        return point.x >= left && point.x <= right
+            && point.y >= bottom && point.y <= top;
+    }
+}
+
+fn spawn_ui(
+    sprites: Res<Sprites>,
+    player_resources: Res<PlayerResources>,
+    mut commands: Commands,
+) {
+    let background = sprites.background.clone().expect("Sprites should be loaded");
+    let frame = sprites.frame.clone().expect("Sprites should be loaded");
+    let remote_base = sprites.remote_base.clone().expect("Sprites should be loaded");
+    let dial = sprites.remote_dial.as_ref().expect("Sprites should be loaded")[0].clone();
+    let handle = sprites.remote_handle.clone().expect("Sprites should be loaded");
+    let waves = sprites.remote_wave_buttons.as_ref().expect("Sprites should be loaded");
+    let toggles = sprites.remote_wave_inverter.as_ref().expect("Sprites should be loaded");
+    let lights = sprites.remote_wave_light.as_ref().expect("Sprites should be loaded");
+    commands.spawn((
+        BackgroundImage,
+        Sprite::from_image(background),
+        Transform::from_xyz(0.0, 0.0, Z_POS_BACKGROUND)
+    ));
+    commands.spawn((
+        Sprite::from_image(frame),
+        Transform::from_xyz(0.0, 0.0, Z_POS_FRAME)
+    ))
+    .with_children(|cmd| {
         cmd.spawn((
             ResourceCounter::Charges,
-            Sprite::from_image(counters[(player_resources.charges) as usize].clone()),
+            CounterOwner { player: 0 },
+            CounterDisplay::new(player_resources.charges as i64),
             Transform::from_xyz(-710.0, 1075.0, 1.0),
+            Visibility::Inherited,
         ));
         cmd.spawn((
             ResourceCounter::Reputation,
-            Sprite::from_image(counters[(player_resources.reputation) as usize].clone()),
+            CounterOwner { player: 0 },
+            CounterDisplay::new(player_resources.reputation as i64),
             Transform::from_xyz(20.0, 1075.0, 1.0),
+            Visibility::Inherited,
+        ));
+        // the second player's pair only shows up in versus
+        cmd.spawn((
+            ResourceCounter::Charges,
+            CounterOwner { player: 1 },
+            CounterDisplay::new(player_resources.charges as i64),
+            Transform::from_xyz(1150.0, 1075.0, 1.0),
+            Visibility::Hidden,
+        ));
+        cmd.spawn((
+            ResourceCounter::Reputation,
+            CounterOwner { player: 1 },
+            CounterDisplay::new(player_resources.reputation as i64),
+            Transform::from_xyz(1500.0, 1075.0, 1.0),
+            Visibility::Hidden,
+        ));
+        cmd.spawn((
+            Text2d::default(),
+            LocalizedText::new("hud.legend_hint"),
+            TextFont::from_font_size(45.0),
+            TextColor(Color::srgba(1.0, 1.0, 1.0, 0.6)),
+            Transform::from_xyz(-1500.0, -1140.0, 1.0),
+        ));
+        for (player, x) in [(0, -950.0), (1, 880.0)] {
+            cmd.spawn((
+                VersusTurnLabel { player },
+                Text2d::default(),
+                LocalizedText::with_args("versus.player_short", vec![(player + 1).to_string()]),
+                TextFont::from_font_size(90.0),
+                Transform::from_xyz(x, 1075.0, 1.0),
+                Visibility::Hidden,
+            ));
+        }
+        cmd.spawn((
+            ResourceCounter::RoundTimer,
+            CounterOwner { player: 0 },
+            CounterDisplay::new(0),
+            Transform::from_xyz(400.0, 1075.0, 1.0),
+            Visibility::Hidden,
+        ));
+        cmd.spawn((
+            ResourceCounter::Score,
+            CounterOwner { player: 0 },
+            CounterDisplay::new(player_resources.score as i64),
+            Transform::from_xyz(1250.0, 1075.0, 1.0),
+            Visibility::Hidden,
+        )).with_child((
+            Text2d::default(),
+            LocalizedText::new("hud.score"),
+            TextFont::from_font_size(70.0),
+            Anchor::CENTER_RIGHT,
+            // clear of the digits up to a three digit score
+            Transform::from_xyz(-1.5 * COUNTER_DIGIT_WIDTH - 20.0, 0.0, 0.0),
         ));
     });
     commands.spawn((
@@ -821,45 +2668,76 @@ fn spawn_ui(
 }
 
 fn reset_game(
-    sprites: Res<Sprites>,
+    catalog: Res<GhostCatalog>,
     mut ui_enabled: ResMut<UIEnabled>,
     mut resources: ResMut<PlayerResources>,
     mut target_ghosts: ResMut<TargetGhostTags>,
     mut ghost_wave: ResMut<GhostWaveConfig>,
+    mut lanes: ResMut<LaneLayout>,
+    mut capture_zones: ResMut<CaptureZones>,
+    mut notes: ResMut<ButtonNotes>,
     game_end_splash: Query<(Entity, &GameEndSplash)>,
     ghosts: Query<Entity, With<Ghost>>,
-    target_displays: Query<&mut Sprite, With<TargetGhostDisplay>>,
+    sfx: Query<Entity, With<Sfx>>,
+    round_markers: Query<Entity, Or<(
+        With<LaneOverlay>,
+        With<CaptureZoneMarker>,
+        With<TargetGhostDisplay>,
+        With<LegendPanel>,
+    )>>,
     mut commands: Commands,
 ) {
     let (splash_entity, game_end) = game_end_splash.single().unwrap();
 
     if *game_end == GameEndSplash::Lose {
         resources.reputation = 5;
+        resources.score = 0;
     }
     resources.charges = 10;
 
     commands.entity(splash_entity).despawn();
 
-    *target_ghosts = choose_target_ghosts();
-    *ghost_wave = build_ghost_wave_config(&target_ghosts);
+    roll_round(
+        &catalog,
+        &mut capture_zones,
+        &mut target_ghosts,
+        &mut ghost_wave,
+        &mut lanes,
+        &mut rand::rng());
 
     for ghost in ghosts {
         commands.entity(ghost).despawn();
     }
-    
-    let ghost_sprites = sprites.ghosts.as_ref().expect("Images should have loaded");
-    let body_idx = (target_ghosts.target.body_tag - TAG_BODY_1) as usize;
-    let hat_idx = target_ghosts.target.hat_tag as usize;
-    let target_ghost_sprite = ghost_sprites[body_idx][hat_idx].clone();
 
-    for mut target_display in target_displays {
-        target_display.image = target_ghost_sprite.clone();
+    for marker in round_markers {
+        commands.entity(marker).despawn();
+    }
+
+    for entity in sfx {
+        commands.entity(entity).despawn();
     }
 
+    *notes = ButtonNotes::default();
+
     ui_enabled.moving_ghosts = false;
     ui_enabled.enabled = true;
 }
 
+// Rerolls everything that makes up a round: where the captures happen, who's being captured,
+// what the remote does and which lanes have hazards
+fn roll_round(
+    catalog: &GhostCatalog,
+    capture_zones: &mut CaptureZones,
+    target_ghosts: &mut TargetGhostTags,
+    ghost_wave: &mut GhostWaveConfig,
+    lanes: &mut LaneLayout,
+    rng: &mut impl Rng,
+) {
+    *capture_zones = choose_capture_zones(catalog, rng);
+    lanes.properties = roll_lane_properties(capture_zones, rng);
+    (*target_ghosts, *ghost_wave) = choose_targets_and_wave(catalog, capture_zones, lanes, rng);
+}
+
 #[derive(Component)]
 struct WaveButtonLight {
     button_idx: i8,
@@ -870,16 +2748,111 @@ struct InverterSwitch {
     button_idx: i8,
 }
 
+// Marks a sprite drawn from a ghost's tags so its tint can follow the palette setting
+#[derive(Component)]
+struct GhostTint(GhostTag);
+
+#[derive(Component)]
+struct BackgroundImage;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum RemoteSwitchKind {
+    Light,
+    Inverter,
+}
+
+// A shape drawn over a light or inverter switch in high contrast mode, only shown while the
+// switch is in the state it stands for
+#[derive(Component)]
+struct StateIndicator {
+    button_idx: i8,
+    kind: RemoteSwitchKind,
+    shown_when: bool,
+}
+
+#[derive(Component)]
+struct StateLabel {
+    button_idx: i8,
+    kind: RemoteSwitchKind,
+}
+
 #[derive(Component)]
 struct FireWaveHandle;
 
 #[derive(Component)]
 struct StrengthDial;
 
-#[derive(Component)]
+#[derive(Component, PartialEq, Eq)]
 enum ResourceCounter {
     Charges,
     Reputation,
+    Score,
+    // seconds left on the clock in timed mode
+    RoundTimer,
+}
+
+#[derive(Component)]
+struct CounterOwner {
+    player: usize,
+}
+
+// Ticks one step at a time towards the real value
+#[derive(Component)]
+struct CounterDisplay {
+    shown: i64,
+    // counts down after each tick, the digits squash and spring back while it does
+    tick_timer: f32,
+}
+impl CounterDisplay {
+    fn new(value: i64) -> CounterDisplay {
+        return CounterDisplay {
+            shown: value,
+            tick_timer: 0.0,
+        };
+    }
+}
+
+#[derive(Component)]
+struct CounterDigit;
+
+#[derive(Component)]
+struct CounterDigitText;
+
+fn spawn_counter_digits(commands: &mut Commands, counter: Entity, sprites: &Sprites, text: &str) {
+    let frame_mesh = sprites.counter_frame_mesh.as_ref().expect("Sprites should be loaded");
+    let frame_material = sprites.counter_frame_material.as_ref().expect("Sprites should be loaded");
+    let panel_mesh = sprites.counter_panel_mesh.as_ref().expect("Sprites should be loaded");
+    let panel_material = sprites.counter_panel_material.as_ref().expect("Sprites should be loaded");
+    let digit_count = text.chars().count();
+    commands.entity(counter).with_children(|cmd| {
+        for (i, digit) in text.chars().enumerate() {
+            let digit_x = (i as f32 - (digit_count - 1) as f32 / 2.0) * COUNTER_DIGIT_WIDTH;
+            cmd.spawn((
+                CounterDigit,
+                Mesh2d(frame_mesh.clone()),
+                MeshMaterial2d(frame_material.clone()),
+                Transform::from_xyz(digit_x, 0.0, 0.0),
+            )).with_children(|cmd| {
+                cmd.spawn((
+                    Mesh2d(panel_mesh.clone()),
+                    MeshMaterial2d(panel_material.clone()),
+                    Transform::from_xyz(0.0, 0.0, 0.1),
+                ));
+                cmd.spawn((
+                    CounterDigitText,
+                    Text2d::new(digit.to_string()),
+                    TextFont::from_font_size(105.0),
+                    TextColor(COUNTER_DIGIT_COLOR),
+                    Transform::from_xyz(0.0, 0.0, 0.2),
+                ));
+            });
+        }
+    });
+}
+
+#[derive(Component)]
+struct VersusTurnLabel {
+    player: usize,
 }
 
 fn spawn_wave_button(
@@ -921,114 +2894,493 @@ fn spawn_wave_button(
                 button_idx,
             }
         ));
-    });
-}
-
+        // the strip under the button holds the player's notes
+        cmd.spawn((
+            NoteMarkRow {
+                button_idx,
+            },
+            Transform::from_xyz(0.0, -92.0, 1.0),
+            Visibility::Inherited,
+            Clickable {
+                clickable_type: ClickableType::EditNotes(button_idx),
+                bounds: Rect::new(-180.0, -30.0, 180.0, 30.0),
+            },
+        ));
+    });
+}
+
+#[derive(Component)]
+struct NoteMarkRow {
+    button_idx: i8,
+}
+
+#[derive(Component)]
+struct NoteMarkIcon;
+
+#[derive(Component)]
+struct NotePicker;
+
+// Bodies show up bare. Hats are worn by a body that isn't in this round, greyed out, so the hat
+// is the only thing that looks familiar
+fn get_note_icon(
+    sprites: &Sprites,
+    catalog: &GhostCatalog,
+    target_ghosts: &TargetGhostTags,
+    tag: GhostTag,
+) -> Sprite {
+    let image = if catalog.hat_tags().contains(&tag) {
+        let ghost_sprites = sprites.ghosts.as_ref().expect("Sprites should be loaded");
+        let stand_in_body = catalog.body_tags()
+            .find(|body_tag| !target_ghosts.all_tags.contains(body_tag))
+            .unwrap_or(catalog.body_tags().start);
+        ghost_sprites[catalog.body_index(stand_in_body)][catalog.hat_index(tag)].clone()
+    } else {
+        let bare_sprites = sprites.bare_ghosts.as_ref().expect("Sprites should be loaded");
+        bare_sprites[catalog.body_index(tag)].clone()
+    };
+    return Sprite {
+        image,
+        color: Color::srgb(0.75, 0.75, 0.8),
+        ..default()
+    };
+}
+
+fn update_note_marks(
+    sprites: Res<Sprites>,
+    catalog: Res<GhostCatalog>,
+    target_ghosts: Res<TargetGhostTags>,
+    notes: Res<ButtonNotes>,
+    rows: Query<(Entity, &NoteMarkRow)>,
+    new_rows: Query<(), Added<NoteMarkRow>>,
+    icons: Query<Entity, With<NoteMarkIcon>>,
+    mut commands: Commands,
+) {
+    if !notes.is_changed() && new_rows.is_empty() {
+        return;
+    }
+    for icon in icons {
+        commands.entity(icon).despawn();
+    }
+    for (row, mark_row) in rows {
+        let marks = &notes.marks[mark_row.button_idx as usize];
+        commands.entity(row).with_children(|cmd| {
+            if marks.is_empty() {
+                cmd.spawn((
+                    NoteMarkIcon,
+                    Text2d::default(),
+                    LocalizedText::new("notes.add"),
+                    TextFont::from_font_size(32.0),
+                    TextColor(Color::srgba(1.0, 1.0, 1.0, 0.35)),
+                    Transform::from_xyz(0.0, 0.0, 0.0),
+                ));
+            }
+            for (i, mark) in marks.iter().enumerate() {
+                let mark_x = -140.0 + 70.0 * i as f32;
+                cmd.spawn((
+                    NoteMarkIcon,
+                    get_note_icon(&sprites, &catalog, &target_ghosts, mark.tag),
+                    Transform::from_xyz(mark_x, 0.0, 0.0)
+                        .with_scale(Vec3::new(0.08, 0.08, 1.0)),
+                )).with_child((
+                    Text2d::new(if mark.direction > 0 { ">" } else { "<" }),
+                    TextFont::from_font_size(300.0),
+                    Transform::from_xyz(300.0, -150.0, 1.0),
+                ));
+            }
+        });
+    }
+}
+
+fn update_note_picker(
+    sprites: Res<Sprites>,
+    strings: Res<Strings>,
+    catalog: Res<GhostCatalog>,
+    target_ghosts: Res<TargetGhostTags>,
+    notes: Res<ButtonNotes>,
+    pickers: Query<Entity, With<NotePicker>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut commands: Commands,
+) {
+    if !notes.is_changed() {
+        return;
+    }
+    for picker in pickers {
+        commands.entity(picker).despawn();
+    }
+    let Some(button) = notes.picker_button else { return };
+
+    const RING_RADIUS: f32 = 330.0;
+    // only bodies and hats get marks, and only the ones in play this round
+    let mut tags: Vec<GhostTag> = target_ghosts.all_tags.iter()
+        .copied()
+        .filter(|tag| !catalog.color_tags().contains(tag))
+        .collect();
+    tags.sort();
+    tags.dedup();
+    commands.spawn((
+        NotePicker,
+        Mesh2d(meshes.add(Circle::new(RING_RADIUS + 110.0))),
+        MeshMaterial2d(materials.add(Color::srgba(0.05, 0.05, 0.1, 0.92))),
+        Transform::from_xyz(500.0, -100.0, Z_POS_NOTE_PICKER),
+    )).with_children(|cmd| {
+        cmd.spawn((
+            Text2d::default(),
+            LocalizedText::with_args(
+                "notes.picker",
+                vec![strings.get(&format!("wave.{}", GHOST_WAVE_NAMES[button])).to_string()]),
+            TextFont::from_font_size(42.0),
+            Transform::from_xyz(0.0, 0.0, 1.0),
+        ));
+        for (i, &tag) in tags.iter().enumerate() {
+            let angle = std::f32::consts::FRAC_PI_2 - 2.0 * std::f32::consts::PI * i as f32 / tags.len() as f32;
+            let pos = Vec2::from_angle(angle) * RING_RADIUS;
+            let mark = notes.marks[button].iter().find(|mark| mark.tag == tag);
+            let mut icon = get_note_icon(&sprites, &catalog, &target_ghosts, tag);
+            if mark.is_some() {
+                icon.color = Color::srgb(1.0, 0.8, 0.2);
+            }
+            cmd.spawn((
+                icon,
+                Transform::from_xyz(pos.x, pos.y, 1.0)
+                    .with_scale(Vec3::new(0.14, 0.14, 1.0)),
+            ));
+            cmd.spawn((
+                Transform::from_xyz(pos.x, pos.y, 1.0),
+                Visibility::Inherited,
+                Clickable {
+                    clickable_type: ClickableType::PickNote(tag),
+                    bounds: Rect::new(-50.0, -50.0, 50.0, 50.0),
+                },
+            ));
+            if let Some(mark) = mark {
+                cmd.spawn((
+                    Text2d::new(if mark.direction > 0 { ">" } else { "<" }),
+                    TextFont::from_font_size(50.0),
+                    TextColor(Color::srgb(1.0, 0.8, 0.2)),
+                    Transform::from_xyz(pos.x + 45.0, pos.y - 40.0, 2.0),
+                ));
+            }
+        }
+    });
+}
+
+// Each click moves a tag along from unmarked, to right, to left and back to unmarked
+fn cycle_note_mark(marks: &mut Vec<NoteMark>, tag: GhostTag) {
+    match marks.iter().position(|mark| mark.tag == tag) {
+        Some(idx) if marks[idx].direction > 0 => marks[idx].direction = -1,
+        Some(idx) => {
+            marks.remove(idx);
+        },
+        None if marks.len() < NOTE_MARKS_PER_BUTTON => marks.push(NoteMark { tag, direction: 1 }),
+        None => {},
+    }
+}
+
 #[derive(Component)]
 struct GhostShadow;
 
+// Picks which way jittery ghosts drift, seeded from the day in the daily challenge so everyone
+// gets the same drifts
+#[derive(Resource)]
+struct DriftRng(StdRng);
+
 fn spawn_ghosts(
     sprites: Res<Sprites>,
+    catalog: Res<GhostCatalog>,
     target_ghost: Res<TargetGhostTags>,
     lanes: Res<LaneLayout>,
+    settings: Res<GameSettings>,
+    daily: Res<DailyChallenge>,
     mut commands: Commands,
 ) {
-    let mut rng = rand::rng();
+    // the daily challenge deals the ghosts out the same way for everyone
+    let mut rng = if settings.mode == GameMode::Daily {
+        daily_rng(daily.day, DAILY_FIELD_SALT)
+    } else {
+        StdRng::from_rng(&mut rand::rng())
+    };
+    commands.insert_resource(DriftRng(if settings.mode == GameMode::Daily {
+        daily_rng(daily.day, DAILY_DRIFT_SALT)
+    } else {
+        StdRng::from_rng(&mut rand::rng())
+    }));
     // Choose 3 lanes to get the target, then randomly distribute the rest of the ghosts across the
     // rest.
-    // Or just grab every variant of ghost, multiply by 3 (since we know we have 5 and need 15) and
-    // randomly distribute them. We may get 2 or even 3 of the target in one lane and that's ok
+    // Or just grab 3 of the target (or 2 of each if there's more than one), fill the rest of the
+    // field with the variants as evenly as we can and randomly distribute them. We may get 2 or
+    // even 3 of the target in one lane and that's ok
     //
     // TODO: generate this randomly based on the lane layout and the number of ghost variants
-
-    let mut ghosts = Vec::new();
-    for _ in 0..3 {
-        ghosts.push(target_ghost.target);
-        ghosts.push(target_ghost.others[0].unwrap());
-        ghosts.push(target_ghost.others[1].unwrap());
-        ghosts.push(target_ghost.others[2].unwrap());
-        ghosts.push(target_ghost.others[3].unwrap());
+    const GHOSTS_PER_LANE: usize = 3;
+    let ghost_count = LANE_LAYOUT_SPAWN_LANES as usize * GHOSTS_PER_LANE;
+    let copies_per_target = if target_ghost.targets.len() == 1 { 3 } else { 2 };
+
+    let others: Vec<(GhostTags, Option<GhostBehaviour>)> = target_ghost.others.iter()
+        .copied()
+        .zip(target_ghost.other_behaviours.iter().copied())
+        .collect();
+    let mut ghosts = Vec::<(GhostTags, Option<GhostBehaviour>)>::new();
+    for target in target_ghost.targets.iter() {
+        for _ in 0..copies_per_target {
+            ghosts.push((*target, None));
+        }
+    }
+    while ghosts.len() < ghost_count {
+        let mut batch = others.clone();
+        batch.shuffle(&mut rng);
+        batch.truncate(ghost_count - ghosts.len());
+        ghosts.append(&mut batch);
     }
     ghosts.shuffle(&mut rng);
-    let ghost_sprites = sprites.ghosts.as_ref().expect("Sprites should be loaded");
-    let shadow_sprite = sprites.shadow.as_ref().expect("Sprites should be loaded");
 
     for lane_index in 0..LANE_LAYOUT_SPAWN_LANES {
-        for _ in 0..3 {
+        for _ in 0..GHOSTS_PER_LANE {
             let lane_index = lane_index + LANE_LAYOUT_BUFFER_LANES;
             let pos = get_random_point_in_rect(&lanes.margined_lanes[lane_index as usize]);
-            let ghost = ghosts.pop().unwrap();
-            let body_idx = (ghost.body_tag - TAG_BODY_1) as usize;
-            let hat_idx = (ghost.hat_tag - TAG_HAT_1) as usize;
-            let sprite = ghost_sprites[body_idx][hat_idx].clone();
-            let radius_x = 100.0 + rand::random::<f32>() * 50.0;
-            let omega_x = std::f32::consts::PI / 8.0 + rand::random::<f32>() * std::f32::consts::PI / 4.0;
-            let theta_x = rand::random::<f32>() * 2.0 * std::f32::consts::PI;
-
-            let radius_y = 120.0 + rand::random::<f32>() * 80.0;
-            let omega_y = std::f32::consts::PI / 4.0 + rand::random::<f32>() * std::f32::consts::PI / 2.0;
-            let theta_y = rand::random::<f32>() * 2.0 * std::f32::consts::PI;
-            commands.spawn((
-                Ghost,
-                Transform::from_xyz(pos.x, pos.y, Z_POS_GHOSTS)
-                    .with_scale(Vec3::new(GHOST_SPRITE_SCALE, GHOST_SPRITE_SCALE, 1.0)),
-                ghost,
-                GhostLanePosition {
-                    lane: lane_index,
-                },
-                Visibility::Visible,
-            ))
-            .with_children(|cmd| {
-                cmd.spawn((
-                    GhostShadow,
-                    Sprite {
-                        image: shadow_sprite.clone(),
-                        color: Color::srgba(0.0, 0.0, 0.0, 0.5),
-                        ..default()
-                    },
-                    Transform::from_xyz(0.0, 0.0, 0.0),
-                    GhostAnimationLoop {
-                        base_scale: GHOST_SHADOW_SCALE,
-                        theta_x,
-                        omega_x,
-                        radius_x,
-                        theta_y,
-                        omega_y,
-                        radius_y: 0.0,
-                        offset_y: 0.0,
-                    }
-                ));
-                cmd.spawn((
-                    Sprite::from_image(sprite),
-                    Transform::from_xyz(0.0, 500.0, 1.0),
-                    GhostAnimationLoop {
-                        base_scale: 1.0,
-                        theta_x,
-                        omega_x,
-                        radius_x,
-                        theta_y,
-                        omega_y,
-                        radius_y,
-                        offset_y: 500.0,
-                    }
-                ));
-            });
+            let (ghost, behaviour) = ghosts.pop().unwrap();
+            spawn_ghost(&mut commands, &sprites, &catalog, ghost, behaviour, lane_index, pos);
+        }
+    }
+}
+
+fn spawn_ghost(
+    commands: &mut Commands,
+    sprites: &Sprites,
+    catalog: &GhostCatalog,
+    ghost: GhostTags,
+    behaviour: Option<GhostBehaviour>,
+    lane_index: u8,
+    pos: Vec2,
+) -> Entity {
+    let shadow_sprite = sprites.shadow.as_ref().expect("Sprites should be loaded");
+    let mut sprite = sprites.ghost(catalog, &ghost);
+    let mut base_scale = 1.0;
+    let mut radius_x = 100.0 + rand::random::<f32>() * 50.0;
+    let mut omega_x = std::f32::consts::PI / 8.0 + rand::random::<f32>() * std::f32::consts::PI / 4.0;
+    let theta_x = rand::random::<f32>() * 2.0 * std::f32::consts::PI;
+
+    let mut radius_y = 120.0 + rand::random::<f32>() * 80.0;
+    let mut omega_y = std::f32::consts::PI / 4.0 + rand::random::<f32>() * std::f32::consts::PI / 2.0;
+    let theta_y = rand::random::<f32>() * 2.0 * std::f32::consts::PI;
+
+    // give each behaviour its own look so they can be told apart on the field
+    match behaviour {
+        Some(GhostBehaviour::Stubborn) => {
+            base_scale = 1.15;
+            radius_y *= 0.2;
+            omega_y *= 0.5;
+        },
+        Some(GhostBehaviour::Jittery { .. }) => {
+            radius_x = 25.0;
+            omega_x = 8.0 * std::f32::consts::PI;
+        },
+        Some(GhostBehaviour::Mirror) => {
+            sprite.flip_x = true;
+        },
+        _ => {},
+    }
+
+    let mut ghost_cmd = commands.spawn((
+        Ghost,
+        Transform::from_xyz(pos.x, pos.y, Z_POS_GHOSTS)
+            .with_scale(Vec3::new(GHOST_SPRITE_SCALE, GHOST_SPRITE_SCALE, 1.0)),
+        ghost,
+        GhostLanePosition {
+            lane: lane_index,
+        },
+        Visibility::Visible,
+    ));
+    let ghost_entity = ghost_cmd.id();
+    if let Some(behaviour) = behaviour {
+        ghost_cmd.insert(behaviour);
+    }
+    ghost_cmd.with_children(|cmd| {
+        cmd.spawn((
+            GhostShadow,
+            Sprite {
+                image: shadow_sprite.clone(),
+                color: Color::srgba(0.0, 0.0, 0.0, 0.5),
+                ..default()
+            },
+            Transform::from_xyz(0.0, 0.0, 0.0),
+            GhostAnimationLoop {
+                base_scale: GHOST_SHADOW_SCALE * base_scale,
+                theta_x,
+                omega_x,
+                radius_x,
+                theta_y,
+                omega_y,
+                radius_y: 0.0,
+                offset_y: 0.0,
+            }
+        ));
+        if behaviour == Some(GhostBehaviour::Twin) {
+            // a faint copy trailing just behind the ghost
+            let mut echo_sprite = sprite.clone();
+            echo_sprite.color = echo_sprite.color.with_alpha(0.4);
+            cmd.spawn((
+                TwinEcho,
+                echo_sprite,
+                GhostTint(ghost.color_tag),
+                Transform::from_xyz(0.0, 500.0, 0.5),
+                GhostAnimationLoop {
+                    base_scale,
+                    theta_x: theta_x - 0.6,
+                    omega_x,
+                    radius_x,
+                    theta_y: theta_y - 0.6,
+                    omega_y,
+                    radius_y,
+                    offset_y: 500.0,
+                }
+            ));
+        }
+        cmd.spawn((
+            sprite,
+            GhostTint(ghost.color_tag),
+            Transform::from_xyz(0.0, 500.0, 1.0),
+            GhostAnimationLoop {
+                base_scale,
+                theta_x,
+                omega_x,
+                radius_x,
+                theta_y,
+                omega_y,
+                radius_y,
+                offset_y: 500.0,
+            }
+        ));
+    });
+    return ghost_entity;
+}
+
+fn reset_endless_spawner(
+    mut spawner: ResMut<EndlessSpawner>,
+) {
+    spawner.firings_until_wave = ENDLESS_FIRINGS_PER_WAVE;
+}
+
+fn schedule_endless_waves(
+    settings: Res<GameSettings>,
+    sprites: Res<Sprites>,
+    catalog: Res<GhostCatalog>,
+    target_ghost: Res<TargetGhostTags>,
+    lanes: Res<LaneLayout>,
+    mut spawner: ResMut<EndlessSpawner>,
+    mut on_fire: MessageReader<RemoteFired>,
+    ghosts: Query<(), With<Ghost>>,
+    mut commands: Commands,
+) {
+    if settings.mode != GameMode::Endless {
+        on_fire.clear();
+        return;
+    }
+    for _ in on_fire.read() {
+        spawner.firings_until_wave = spawner.firings_until_wave.saturating_sub(1);
+        if spawner.firings_until_wave > 0 {
+            continue;
+        }
+        spawner.firings_until_wave = ENDLESS_FIRINGS_PER_WAVE;
+        // skip the wave rather than bury the field
+        if ghosts.iter().count() + ENDLESS_WAVE_SIZE <= ENDLESS_MAX_GHOSTS {
+            spawn_endless_wave(&mut commands, &sprites, &catalog, &target_ghost, &lanes);
         }
     }
 }
 
+// Floats a handful of ghosts in from off screen to the buffer lanes, always bringing one more
+// target along so the player can keep capturing
+fn spawn_endless_wave(
+    commands: &mut Commands,
+    sprites: &Sprites,
+    catalog: &GhostCatalog,
+    target_ghost: &TargetGhostTags,
+    lanes: &LaneLayout,
+) {
+    let mut rng = rand::rng();
+    // anything arriving behind a wall could never be pushed over to a capture zone
+    let mut entry_lanes: Vec<u8> = (0..LANE_LAYOUT_BUFFER_LANES)
+        .chain(LANE_LAYOUT_LANE_COUNT - LANE_LAYOUT_BUFFER_LANES..LANE_LAYOUT_LANE_COUNT)
+        .filter(|&lane| {
+            return (lane.min(LANE_LAYOUT_CENTER_LANE)..=lane.max(LANE_LAYOUT_CENTER_LANE))
+                .all(|x| lanes.properties[x as usize] != LaneProperty::Blocked);
+        })
+        .collect();
+    if entry_lanes.is_empty() {
+        // walled off on both sides, so they float in past the walls instead
+        entry_lanes = vec![LANE_LAYOUT_BUFFER_LANES, LANE_LAYOUT_LANE_COUNT - LANE_LAYOUT_BUFFER_LANES - 1];
+    }
+    let target = *target_ghost.targets.choose(&mut rng).unwrap();
+    let mut arrivals = vec![(target, None)];
+    while arrivals.len() < ENDLESS_WAVE_SIZE {
+        let other_idx = rng.random_range(0..target_ghost.others.len());
+        arrivals.push((target_ghost.others[other_idx], target_ghost.other_behaviours[other_idx]));
+    }
+    for (ghost, behaviour) in arrivals {
+        let lane = *entry_lanes.choose(&mut rng).unwrap();
+        let entry_x = if lane < LANE_LAYOUT_CENTER_LANE {
+            LANE_LAYOUT_DESPAWN_LEFT
+        } else {
+            LANE_LAYOUT_DESPAWN_RIGHT
+        };
+        let random_y = rng.random::<f32>() * LANE_LAYOUT_HEIGHT - LANE_LAYOUT_HEIGHT / 2.0;
+        let ghost_entity = spawn_ghost(commands, sprites, catalog, ghost, behaviour, lane, Vec2::new(entry_x, random_y));
+        commands.entity(ghost_entity).insert(GhostScooting {
+            scoot_target: get_random_point_in_rect(&lanes.margined_lanes[lane as usize]),
+            movement_speed: 400.0,
+        });
+    }
+}
+
 #[derive(Component)]
 struct Music;
 
+// A quiet taste of what the remote will sound like, played whenever it's adjusted
+#[derive(Component)]
+struct HumPreview;
+
+fn update_hum_preview(
+    ghost_wave: Res<GhostWaveConfig>,
+    ui_enabled: Res<UIEnabled>,
+    mut synths: ResMut<Assets<WaveSynth>>,
+    hums: Query<Entity, With<HumPreview>>,
+    mut commands: Commands,
+) {
+    // the real thing takes over once the remote's been fired
+    if !ghost_wave.is_changed() && !ui_enabled.moving_ghosts {
+        return;
+    }
+    for hum in hums {
+        commands.entity(hum).despawn();
+    }
+    let synth = WaveSynth::from_remote(&ghost_wave, Some(HUM_PREVIEW_SECONDS));
+    if ui_enabled.moving_ghosts || synth.voices.is_empty() {
+        return;
+    }
+    commands.spawn((
+        HumPreview,
+        AudioPlayer(synths.add(synth)),
+        PlaybackSettings::DESPAWN.with_volume(bevy::audio::Volume::Linear(HUM_PREVIEW_VOLUME)),
+    ));
+}
+
 fn spawn_music(
     audio: Res<AudioHandles>,
+    audio_sources: Res<Assets<AudioSource>>,
+    controls: Res<MusicControls>,
+    mut music_assets: ResMut<Assets<AdaptiveMusic>>,
     mut commands: Commands,
 ) {
     let music = audio.music.as_ref().expect("Audio should be loaded");
+    let track = audio_sources.get(music).expect("Audio should be loaded").clone();
     commands.spawn((
         Music,
-        AudioPlayer::new(music.clone()),
-        PlaybackSettings::LOOP,
+        AudioPlayer(music_assets.add(AdaptiveMusic {
+            track,
+            controls: controls.clone(),
+        })),
+        PlaybackSettings::ONCE,
     ));
 }
 
@@ -1040,6 +3392,12 @@ fn spawn_camera(
         Camera::default(),
         Transform::from_scale(Vec3::new(3.0, 3.0, 1.0)),
     ));
+    // the camera's scale follows the window, so the ears get their own entity to stay put at
+    // either edge of the game area
+    commands.spawn((
+        SpatialListener::new(GAME_AREA_WIDTH),
+        Transform::default(),
+    ));
 }
 
 #[derive(PartialEq, Eq, Component)]
@@ -1086,21 +3444,24 @@ fn despawn_loading_bar(
 
 fn animate_ghosts(
     ghosts: Query<(&mut Transform, &mut GhostAnimationLoop)>,
+    save_data: Res<SaveData>,
     time: Res<Time>,
 ) {
     const GHOST_SQUIDGE_RADIUS: f32 = 0.03;
 
+    let bob_scale = if save_data.accessibility.reduced_motion { REDUCED_MOTION_BOB_SCALE } else { 1.0 };
     for (mut transform, mut ghost_anim) in ghosts {
         ghost_anim.theta_y += ghost_anim.omega_y * time.delta_secs();
         ghost_anim.theta_x += ghost_anim.omega_x * time.delta_secs();
-        transform.translation.y = ghost_anim.theta_y.sin() * ghost_anim.radius_y + ghost_anim.offset_y;
-        transform.translation.x = ghost_anim.theta_x.sin() * ghost_anim.radius_x;
-        transform.scale.x = ghost_anim.base_scale + (ghost_anim.theta_y * 2.0).sin() * GHOST_SQUIDGE_RADIUS;
-        transform.scale.y = ghost_anim.base_scale + (ghost_anim.theta_y * 2.0 + std::f32::consts::PI / 2.0).sin() * GHOST_SQUIDGE_RADIUS;
+        transform.translation.y = ghost_anim.theta_y.sin() * ghost_anim.radius_y * bob_scale + ghost_anim.offset_y;
+        transform.translation.x = ghost_anim.theta_x.sin() * ghost_anim.radius_x * bob_scale;
+        let squidge = GHOST_SQUIDGE_RADIUS * bob_scale;
+        transform.scale.x = ghost_anim.base_scale + (ghost_anim.theta_y * 2.0).sin() * squidge;
+        transform.scale.y = ghost_anim.base_scale + (ghost_anim.theta_y * 2.0 + std::f32::consts::PI / 2.0).sin() * squidge;
     }
 }
 
-fn add_to_tag_moves(tag_moves: &mut HashMap::<i8, i8>, button: &ButtonConfig) {
+fn add_to_tag_moves(tag_moves: &mut HashMap::<GhostTag, i8>, button: &ButtonConfig) {
     if button.enabled {
         for interaction in button.interactions.iter() {
             if let Some(tag) = interaction {
@@ -1115,83 +3476,232 @@ fn add_to_tag_moves(tag_moves: &mut HashMap::<i8, i8>, button: &ButtonConfig) {
     }
 }
 
+fn get_ghost_lane_change(tag_moves: &HashMap::<GhostTag, i8>, ghost_tags: &GhostTags, wave_strength: i8) -> i8 {
+    let mut move_acc = 0i8;
+    for tag in ghost_tags.tags() {
+        if let Some(lane_change) = tag_moves.get(&tag) {
+            move_acc += lane_change * wave_strength;
+        }
+    }
+    return move_acc;
+}
+
+fn apply_ghost_behaviour(behaviour: Option<&GhostBehaviour>, move_acc: i8, wave_strength: i8) -> i8 {
+    match behaviour {
+        Some(GhostBehaviour::Stubborn) if wave_strength <= 1 => 0,
+        Some(GhostBehaviour::Mirror) => -move_acc,
+        _ => move_acc,
+    }
+}
+
 #[derive(Message)]
 struct RemoteFired;
 
+// The wave icons of every button that pushed a ghost, carried over its head while it moves
+#[derive(Component)]
+struct WaveBadge;
+
+// Enabled buttons that share at least one tag with the ghost, whether or not they cancelled out
+fn get_contributing_buttons(ghost_wave: &GhostWaveConfig, ghost_tags: &GhostTags) -> Vec<usize> {
+    let tags = ghost_tags.tags();
+    return (0..ghost_wave.buttons.len())
+        .filter(|&idx| {
+            let button = &ghost_wave.buttons[idx];
+            return button.enabled && button.interactions.iter().flatten().any(|tag| tags.contains(tag));
+        })
+        .collect();
+}
+
+// How many lanes the last firing pushed a ghost, before any lanes got in the way
+#[derive(Component)]
+struct LastMove {
+    lanes: i8,
+}
+
+#[derive(Component)]
+struct MoveLabel {
+    fade_timer: f32,
+}
+
+fn spawn_move_labels(
+    ghosts: Query<(Entity, &LastMove, Option<&Children>), Changed<LastMove>>,
+    labels: Query<(), With<MoveLabel>>,
+    mut commands: Commands,
+) {
+    for (ghost, last_move, children) in ghosts {
+        for &child in children.into_iter().flatten() {
+            if labels.contains(child) {
+                commands.entity(child).despawn();
+            }
+        }
+        let text = if last_move.lanes > 0 {
+            format!("+{} >", last_move.lanes)
+        } else {
+            format!("< {}", last_move.lanes)
+        };
+        commands.entity(ghost).with_child((
+            MoveLabel {
+                fade_timer: MOVE_LABEL_FADE_SECONDS,
+            },
+            Text2d::new(text),
+            TextFont::from_font_size(150.0),
+            TextColor(Color::srgb(1.0, 0.9, 0.5)),
+            Transform::from_xyz(0.0, -220.0, 3.0),
+        ));
+    }
+}
+
+// Labels hold steady while their ghost is on the move, then fade out once it settles
+fn fade_move_labels(
+    time: Res<Time>,
+    labels: Query<(Entity, &ChildOf, &mut MoveLabel, &mut TextColor)>,
+    scooting: Query<(), With<GhostScooting>>,
+    mut commands: Commands,
+) {
+    for (label_entity, parent, mut label, mut color) in labels {
+        if scooting.contains(parent.parent()) {
+            continue;
+        }
+        label.fade_timer -= time.delta_secs();
+        if label.fade_timer <= 0.0 {
+            commands.entity(label_entity).despawn();
+            continue;
+        }
+        color.0 = color.0.with_alpha(label.fade_timer / MOVE_LABEL_FADE_SECONDS);
+    }
+}
+
+fn update_wave_badges(
+    time: Res<Time>,
+    badges: Query<(Entity, &ChildOf, &mut Transform), With<WaveBadge>>,
+    scooting: Query<(), With<GhostScooting>>,
+    mut commands: Commands,
+) {
+    for (badge, parent, mut transform) in badges {
+        if !scooting.contains(parent.parent()) {
+            commands.entity(badge).despawn();
+            continue;
+        }
+        transform.translation.y = WAVE_BADGE_HEIGHT + (time.elapsed_secs() * 8.0).sin() * 40.0;
+    }
+}
+
 #[derive(Component)]
 struct WanderingOff;
 
+fn describe_ghost_move(
+    catalog: &GhostCatalog,
+    strings: &Strings,
+    tags: &GhostTags,
+    from_lane: i8,
+    to_lane: i8,
+) -> String {
+    let ghost = catalog.display_name(tags, strings);
+    if to_lane < 0 {
+        return strings.format("narration.wandered_left", &[&ghost]);
+    }
+    if to_lane >= LANE_LAYOUT_LANE_COUNT as i8 {
+        return strings.format("narration.wandered_right", &[&ghost]);
+    }
+    let distance = (to_lane - from_lane).abs();
+    let lane = to_lane + 1;
+    return match (distance == 1, to_lane > from_lane) {
+        (true, true) => strings.format("narration.moved_one_right", &[&ghost, &lane]),
+        (true, false) => strings.format("narration.moved_one_left", &[&ghost, &lane]),
+        (false, true) => strings.format("narration.moved_right", &[&ghost, &distance, &lane]),
+        (false, false) => strings.format("narration.moved_left", &[&ghost, &distance, &lane]),
+    };
+}
+
 fn begin_scooting_ghosts(
-    audio: Res<AudioHandles>,
+    mut synths: ResMut<Assets<WaveSynth>>,
+    sprites: Res<Sprites>,
+    catalog: Res<GhostCatalog>,
     mut on_fire: MessageReader<RemoteFired>,
-    ghosts: Query<(Entity, &GhostTags, &mut GhostLanePosition), (With<Ghost>, Without<GhostScooting>)>,
+    mut ghosts: Query<(
+        Entity,
+        &GhostTags,
+        &mut GhostLanePosition,
+        Option<&mut GhostBehaviour>,
+        &Transform,
+    ), (With<Ghost>, Without<GhostScooting>)>,
+    twin_echoes: Query<(Entity, &ChildOf), With<TwinEcho>>,
     lanes: Res<LaneLayout>,
     ghost_wave: Res<GhostWaveConfig>,
     target_ghost: Res<TargetGhostTags>,
+    settings: Res<GameSettings>,
     mut resources: ResMut<PlayerResources>,
     mut commands: Commands,
     mut on_lose: MessageWriter<GameLost>,
+    (mut on_sfx, mut on_narrate): (MessageWriter<PlaySfx>, MessageWriter<Narrate>),
+    strings: Res<Strings>,
+    mut drift_rng: ResMut<DriftRng>,
 ) {
     if on_fire.is_empty() {
         return;
     }
     on_fire.clear();
 
-    let mut tag_moves = HashMap::<i8, i8>::new();
+    let mut tag_moves = HashMap::<GhostTag, i8>::new();
     add_to_tag_moves(&mut tag_moves, &ghost_wave.buttons[0]);
     add_to_tag_moves(&mut tag_moves, &ghost_wave.buttons[1]);
     add_to_tag_moves(&mut tag_moves, &ghost_wave.buttons[2]);
     add_to_tag_moves(&mut tag_moves, &ghost_wave.buttons[3]);
     add_to_tag_moves(&mut tag_moves, &ghost_wave.buttons[4]);
     
-    let mut waves = Vec::<Handle<AudioSource>>::new();
-    for i in 0..ghost_wave.buttons.len() {
-        let button = &ghost_wave.buttons[i];
-        if button.enabled {
-            let handle = 
-                match (i, button.inverted) {
-                    (0, false) => audio.rectified.as_ref(),
-                    (0, true) => audio.rectified_inv.as_ref(),
-                    (1, false) => audio.sawtooth.as_ref(),
-                    (1, true) => audio.sawtooth_inv.as_ref(),
-                    (2, false) => audio.sine.as_ref(),
-                    (2, true) => audio.sine_inv.as_ref(),
-                    (3, false) => audio.square.as_ref(),
-                    (3, true) => audio.square_inv.as_ref(),
-                    (_, false) => audio.triangle.as_ref(),
-                    (_, true) => audio.triangle_inv.as_ref(),
-                };
-            let handle = handle.expect("Audio should be loaded").clone();
-            waves.push(handle);
-        }
-    }
-
     let wave_strength = ghost_wave.dial_strength as i8;
-    let mut wave_fired = false;
-    for (ghost_entity, ghost_tags, mut ghost_lane_pos) in ghosts {
+    let wave_fired = ghosts.iter().any(|(_, ghost_tags, _, behaviour, _)| {
+        let move_acc = get_ghost_lane_change(&tag_moves, ghost_tags, wave_strength);
+        return apply_ghost_behaviour(behaviour, move_acc, wave_strength) != 0;
+    });
+    let mut twin_splits = Vec::<(Entity, GhostTags, u8, Vec2)>::new();
+    let mut escaped_targets = 0u8;
+    let mut wave_badges = Vec::<(Entity, Vec<usize>)>::new();
+    let mut moves_narration = Vec::<String>::new();
+    for (ghost_entity, ghost_tags, mut ghost_lane_pos, behaviour, transform) in &mut ghosts {
         if let Ok(mut ghost_cmd) = commands.get_entity(ghost_entity) {
-            let mut move_acc = 0i8;
-            if let Some(lane_change) = tag_moves.get(&ghost_tags.body_tag) {
-                move_acc += lane_change * wave_strength;
-            }
-            if let Some(lane_change) = tag_moves.get(&ghost_tags.hat_tag) {
-                move_acc += lane_change * wave_strength;
+            let move_acc = get_ghost_lane_change(&tag_moves, ghost_tags, wave_strength);
+            let mut move_acc = apply_ghost_behaviour(behaviour.as_deref(), move_acc, wave_strength);
+            if let Some(mut behaviour) = behaviour {
+                match *behaviour {
+                    GhostBehaviour::Jittery { every, ref mut firings } if wave_fired => {
+                        *firings += 1;
+                        if *firings >= every {
+                            *firings = 0;
+                            move_acc += *[-1, 1].choose(&mut drift_rng.0).unwrap();
+                        }
+                    },
+                    GhostBehaviour::Twin if move_acc != 0 => {
+                        twin_splits.push((
+                            ghost_entity,
+                            *ghost_tags,
+                            ghost_lane_pos.lane,
+                            transform.translation.xy()));
+                        ghost_cmd.remove::<GhostBehaviour>();
+                    },
+                    _ => {},
+                }
             }
             if move_acc != 0 {
-                wave_fired = true;
+                ghost_cmd.insert(LastMove { lanes: move_acc });
                 // apply the move component 
-                let is_target = 
-                    ghost_tags.body_tag == target_ghost.target.body_tag
-                    && ghost_tags.hat_tag == target_ghost.target.hat_tag;
+                let is_target = target_ghost.is_target(ghost_tags);
                 let ghost_lane = ghost_lane_pos.lane as i8;
-                let new_lane_idx = ghost_lane + move_acc;
+                let new_lane_idx = lanes.resolve_lane_move(ghost_lane_pos.lane, move_acc);
                 if new_lane_idx == ghost_lane {
                     continue;
-                } else if new_lane_idx < 0 {
+                }
+                wave_badges.push((ghost_entity, get_contributing_buttons(&ghost_wave, ghost_tags)));
+                moves_narration.push(describe_ghost_move(&catalog, &strings, ghost_tags, ghost_lane, new_lane_idx));
+                if new_lane_idx < 0 {
                     if is_target {
-                        on_lose.write(GameLost);
+                        escaped_targets += 1;
                     }
                     let random_y = rand::random::<f32>() * LANE_LAYOUT_HEIGHT - LANE_LAYOUT_HEIGHT / 2.0;
+                    on_sfx.write(PlaySfx {
+                        sound: SfxSound::WanderOff,
+                        position: Some(transform.translation.xy()),
+                    });
                     ghost_cmd.insert((
                         WanderingOff,
                         GhostScooting {
@@ -1205,9 +3715,13 @@ fn begin_scooting_ghosts(
                     ghost_cmd.remove::<GhostLanePosition>();
                 } else if new_lane_idx >= LANE_LAYOUT_LANE_COUNT as i8 {
                     if is_target {
-                        on_lose.write(GameLost);
+                        escaped_targets += 1;
                     }
                     let random_y = rand::random::<f32>() * LANE_LAYOUT_HEIGHT - LANE_LAYOUT_HEIGHT / 2.0;
+                    on_sfx.write(PlaySfx {
+                        sound: SfxSound::WanderOff,
+                        position: Some(transform.translation.xy()),
+                    });
                     ghost_cmd.insert((
                         WanderingOff,
                         GhostScooting {
@@ -1227,24 +3741,86 @@ fn begin_scooting_ghosts(
                             movement_speed: 600.0,
                         });
                     ghost_lane_pos.lane = new_lane_idx as u8;
+                    on_sfx.write(PlaySfx {
+                        sound: SfxSound::Whoosh,
+                        position: Some(transform.translation.xy()),
+                    });
                 }
             }
         }
     }
     if wave_fired {
-        resources.charges -= 1;
-        if resources.charges <= 0 {
+        let narration = if moves_narration.is_empty() {
+            strings.get("narration.no_moves").to_string()
+        } else {
+            moves_narration.join(". ")
+        };
+        on_narrate.write(Narrate(narration));
+    }
+    let wave_sprites = sprites.wave_particles.as_ref().expect("Sprites should be loaded");
+    for (ghost_entity, buttons) in wave_badges {
+        if buttons.is_empty() {
+            continue;
+        }
+        commands.entity(ghost_entity).with_children(|cmd| {
+            cmd.spawn((
+                WaveBadge,
+                Transform::from_xyz(0.0, WAVE_BADGE_HEIGHT, 2.0),
+                Visibility::Inherited,
+            )).with_children(|cmd| {
+                for (i, &button) in buttons.iter().enumerate() {
+                    let badge_x = (i as f32 - (buttons.len() - 1) as f32 / 2.0) * 220.0;
+                    cmd.spawn((
+                        Sprite::from_image(wave_sprites[button].clone()),
+                        Transform::from_xyz(badge_x, 0.0, 0.0)
+                            .with_scale(Vec3::new(1.5, 1.5, 1.0)),
+                    ));
+                }
+            });
+        });
+    }
+    for (ghost_entity, ghost_tags, lane, pos) in twin_splits {
+        // the twin's echo becomes a real ghost, left behind in the lane it split from
+        for (echo, echo_parent) in twin_echoes {
+            if echo_parent.parent() == ghost_entity {
+                commands.entity(echo).despawn();
+            }
+        }
+        spawn_ghost(&mut commands, &sprites, &catalog, ghost_tags, None, lane, pos);
+    }
+    let endless = settings.mode == GameMode::Endless;
+    // endless runs only end when reputation does, so escapes and empty charges cost that instead
+    let mut reputation_lost = 0u8;
+    if escaped_targets > 0 {
+        if endless {
+            reputation_lost += escaped_targets;
+        } else {
             on_lose.write(GameLost);
         }
-        let volume = 0.3 + 0.7 * 1.0 / waves.len() as f32;
-        for wave in waves {
-            commands.spawn((
-                Sfx,
-                AudioPlayer::new(wave),
-                PlaybackSettings::LOOP.with_volume(bevy::audio::Volume::Linear(volume)),
-                Lifetime::new(2.0),
-            ));
+    }
+    if wave_fired {
+        if endless && resources.charges == 0 {
+            reputation_lost += 1;
+        } else {
+            resources.charges -= 1;
+            if resources.charges <= 0 && !endless {
+                on_lose.write(GameLost);
+            }
+        }
+    }
+    if reputation_lost > 0 {
+        resources.reputation = resources.reputation.saturating_sub(reputation_lost);
+        if resources.reputation == 0 {
+            on_lose.write(GameLost);
         }
+    }
+    if wave_fired {
+        commands.spawn((
+            Sfx,
+            AudioPlayer(synths.add(WaveSynth::from_remote(&ghost_wave, Some(2.0)))),
+            PlaybackSettings::ONCE.with_volume(bevy::audio::Volume::Linear(0.6)),
+            Lifetime::new(2.0),
+        ));
         commands.spawn((
             Transform::from_xyz(1450.0, 760.0, Z_POS_DEVICE_BACK + 1.0),
             WaveEmitter {
@@ -1319,51 +3895,74 @@ fn handle_remote_clicks(
     query: Query<(&GlobalTransform, &Clickable)>,
     mut on_capture_fire: MessageWriter<CaptureGhostsInitialized>,
     mut on_remote_fire: MessageWriter<RemoteFired>,
+    mut notes: ResMut<ButtonNotes>,
+    mut on_sfx: MessageWriter<PlaySfx>,
     ui_enabled: Res<UIEnabled>,
 ) {
     if !ui_enabled.enabled {
         return;
     }
-    if mouse_button.just_pressed(MouseButton::Left) {
-        if let Ok((camera, camera_transform)) = camera.single() {
-            if let Ok(window) = window.single() {
-                if let Some(cursor_pos) = window.cursor_position() {
-                    if let Ok(cursor_pos) = camera.viewport_to_world_2d(camera_transform, cursor_pos) {
-                        for (clickable_transform, clickable) in query {
-                            let clickable_pos = clickable_transform.translation();
-                            let left = clickable_pos.x + clickable.bounds.min.x;
-                            let right = clickable_pos.x + clickable.bounds.max.x;
-                            let bottom = clickable_pos.y + clickable.bounds.min.y;
-                            let top = clickable_pos.y + clickable.bounds.max.y;
-
-                            if cursor_pos.x >= left && cursor_pos.x <= right
-                                && cursor_pos.y >= bottom && cursor_pos.y <= top
-                            {
-                                match clickable.clickable_type {
-                                    ClickableType::Dial => { 
-                                        buttons.dial_strength = (buttons.dial_strength % 3) + 1;
-                                    },
-                                    ClickableType::WaveEnable(idx) => { 
-                                        buttons.buttons[idx as usize].enabled = !buttons.buttons[idx as usize].enabled;
-                                    },
-                                    ClickableType::WaveInvert(idx) => {
-                                        buttons.buttons[idx as usize].inverted = !buttons.buttons[idx as usize].inverted;
-                                    },
-                                    ClickableType::CaptureGhosts => {
-                                        on_capture_fire.write(CaptureGhostsInitialized);
-                                    },
-                                    ClickableType::FireWave => {
-                                        on_remote_fire.write(RemoteFired);
-                                    }
-                                };
-                            }
-                        }
-                    }
-                }
-            }
-        }
+    if !mouse_button.just_pressed(MouseButton::Left) {
+        return;
     }
-}
+    let Some(cursor_pos) = get_cursor_world_pos(&window, &camera) else { return };
+    // while the note picker is up it gets every click, and clicking anywhere else closes it
+    if let Some(picker_button) = notes.picker_button {
+        let picked = query.iter()
+            .filter(|(clickable_transform, clickable)| clickable.contains(clickable_transform, cursor_pos))
+            .find_map(|(_, clickable)| match clickable.clickable_type {
+                ClickableType::PickNote(tag) => Some(tag),
+                _ => None,
+            });
+        match picked {
+            Some(tag) => cycle_note_mark(&mut notes.marks[picker_button], tag),
+            None => notes.picker_button = None,
+        }
+        on_sfx.write(PlaySfx { sound: SfxSound::Click, position: None });
+        return;
+    }
+    for (clickable_transform, clickable) in query {
+        if !clickable.contains(clickable_transform, cursor_pos) {
+            continue;
+        }
+        match clickable.clickable_type {
+            ClickableType::Dial => { 
+                buttons.dial_strength = (buttons.dial_strength % 3) + 1;
+                on_sfx.write(PlaySfx { sound: SfxSound::Dial, position: None });
+            },
+            ClickableType::WaveEnable(idx) => { 
+                buttons.buttons[idx as usize].enabled = !buttons.buttons[idx as usize].enabled;
+                on_sfx.write(PlaySfx { sound: SfxSound::Click, position: None });
+            },
+            ClickableType::WaveInvert(idx) => {
+                buttons.buttons[idx as usize].inverted = !buttons.buttons[idx as usize].inverted;
+                on_sfx.write(PlaySfx { sound: SfxSound::Toggle, position: None });
+            },
+            ClickableType::CaptureGhosts(zone) => {
+                on_capture_fire.write(CaptureGhostsInitialized { zone });
+                on_sfx.write(PlaySfx { sound: SfxSound::Handle, position: None });
+            },
+            ClickableType::FireWave => {
+                on_remote_fire.write(RemoteFired);
+                on_sfx.write(PlaySfx { sound: SfxSound::Handle, position: None });
+            },
+            ClickableType::EditNotes(idx) => {
+                notes.picker_button = Some(idx as usize);
+                on_sfx.write(PlaySfx { sound: SfxSound::Click, position: None });
+            },
+            ClickableType::SelectMode(_) | ClickableType::PickNote(_) => {},
+        };
+    }
+}
+
+fn get_cursor_world_pos(
+    window: &Query<&Window, With<PrimaryWindow>>,
+    camera: &Query<(&Camera, &GlobalTransform)>,
+) -> Option<Vec2> {
+    let (camera, camera_transform) = camera.single().ok()?;
+    let cursor_pos = window.single().ok()?.cursor_position()?;
+    return camera.viewport_to_world_2d(camera_transform, cursor_pos).ok();
+}
 
 #[derive(Message)]
 struct GameWon;
@@ -1380,28 +3979,33 @@ fn capture_ghosts(
     mut on_capture_fired: MessageReader<CaptureGhostsInitialized>,
     ghosts: Query<(Entity, &GhostLanePosition, &GhostTags)>,
     target: Res<TargetGhostTags>,
+    capture_zones: Res<CaptureZones>,
+    settings: Res<GameSettings>,
     mut player_resources: ResMut<PlayerResources>,
     mut on_win: MessageWriter<GameWon>,
     mut on_lose: MessageWriter<GameLost>,
     mut on_capture: MessageWriter<GhostCaptured>,
 ) {
-    if on_capture_fired.is_empty() {
-        return;
-    }
-    on_capture_fired.clear();
+    let Some(capture) = on_capture_fired.read().last() else { return };
+    let zone = &capture_zones.zones[capture.zone];
     let mut any_ghosts_captured = false;
     let mut points_delta = 0i8;
+    let mut targets_captured = 0u8;
+    let mut targets_misplaced = false;
     let mut target_ghosts_exist_in_other_lanes = false;
     for (entity, ghost_lane, ghost_tags) in ghosts {
-        let is_target = 
-            ghost_tags.body_tag == target.target.body_tag
-            && ghost_tags.hat_tag == target.target.hat_tag;
-        if ghost_lane.lane == 4 { //5th, center lane
+        let is_target = target.is_target(ghost_tags);
+        if ghost_lane.lane == zone.lane {
             any_ghosts_captured = true;
-            if is_target {
-                points_delta += 1;
+            // other targets still count against you if they're caught in the wrong zone
+            let zone_target = zone.targets.iter()
+                .find(|&&target_idx| target.targets[target_idx] == *ghost_tags);
+            if let Some(&target_idx) = zone_target {
+                points_delta += target.rewards[target_idx] as i8;
+                targets_captured += 1;
             } else {
                 points_delta -= 2;
+                targets_misplaced |= is_target;
             }
             on_capture.write(GhostCaptured{ entity });
         } else {
@@ -1411,13 +4015,24 @@ fn capture_ghosts(
         }
     }
     if any_ghosts_captured {
+        let endless = settings.mode == GameMode::Endless;
         if (player_resources.reputation as i8) + points_delta <= 0 {
             player_resources.reputation = 0;
             on_lose.write(GameLost);
         } else {
+            // reputation has always topped out at 10 so one great capture can't bank a whole
+            // round's worth of mistakes
             player_resources.reputation = (player_resources.reputation as i8 + points_delta).min(10) as u8;
+            if targets_misplaced && !endless {
+                // a target in the wrong zone is as good as one that wandered off
+                on_lose.write(GameLost);
+            }
         }
-        if !target_ghosts_exist_in_other_lanes {
+        if endless {
+            // there's always another target coming, so captures refill the remote instead
+            player_resources.charges = player_resources.charges.saturating_add(targets_captured * ENDLESS_CHARGES_PER_TARGET);
+            player_resources.score += targets_captured as u32;
+        } else if !target_ghosts_exist_in_other_lanes && !targets_misplaced {
             on_win.write(GameWon);
         }
     }
@@ -1440,6 +4055,7 @@ fn update_wave_particle_emitters(
     time: Res<Time>,
     sprites: Res<Sprites>,
     emitters: Query<(&mut WaveEmitter, &Transform)>,
+    save_data: Res<SaveData>,
     mut commands: Commands,
 ) {
     let del = time.delta_secs();
@@ -1448,7 +4064,11 @@ fn update_wave_particle_emitters(
     for (mut emitter, transform) in emitters {
         emitter.timer -= del;
         if emitter.timer <= 0.0 {
-            emitter.timer = emitter.emit_period;
+            emitter.timer = if save_data.accessibility.reduced_motion {
+                emitter.emit_period.max(REDUCED_MOTION_EMIT_PERIOD)
+            } else {
+                emitter.emit_period
+            };
             let wave_type = (0..5).filter(|&x| emitter.enabled[x]).choose(&mut rng).unwrap();
             let wave_sprite = wave_sprites[wave_type].clone();
             let angle = rng.random::<f32>() * std::f32::consts::PI;
@@ -1497,11 +4117,17 @@ struct BurstParticle {
 #[derive(Component)]
 struct FlashEffect;
 
+#[derive(Component)]
+struct FlashFade;
+
 fn handle_ghosts_captured(
     sprites: Res<Sprites>,
+    save_data: Res<SaveData>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
     mut on_capture: MessageReader<GhostCaptured>,
-    ghost_roots: Query<(Entity, &Children), With<Ghost>>,
-    ghost_sprites: Query<&GlobalTransform, (With<GhostAnimationLoop>, Without<GhostShadow>)>,
+    ghost_roots: Query<(Entity, &Children, &GlobalTransform), With<Ghost>>,
+    ghost_sprites: Query<&GlobalTransform, (With<GhostAnimationLoop>, Without<GhostShadow>, Without<TwinEcho>)>,
+    mut on_sfx: MessageWriter<PlaySfx>,
     mut commands: Commands,
 ) {
     if on_capture.is_empty() {
@@ -1509,18 +4135,34 @@ fn handle_ghosts_captured(
     }
     let rect_mesh = sprites.flash_mesh.as_ref().expect("Images should be loaded");
     let rect_color = sprites.flash_material.as_ref().expect("Images should be loaded");
-    commands.spawn((
-        FlashEffect,
-        Lifetime::new(0.07),
-        Mesh2d(rect_mesh.clone()),
-        MeshMaterial2d(rect_color.clone()),
-        Transform::from_xyz(0.0, 0.0, Z_POS_GHOSTS + 3.0),
-    ));
+    if save_data.accessibility.reduced_motion {
+        // a faint wash that eases in and out instead of a white flash
+        commands.spawn((
+            FlashEffect,
+            FlashFade,
+            Lifetime::new(REDUCED_MOTION_FADE_SECONDS),
+            Mesh2d(rect_mesh.clone()),
+            MeshMaterial2d(materials.add(Color::WHITE.with_alpha(0.0))),
+            Transform::from_xyz(0.0, 0.0, Z_POS_GHOSTS + 3.0),
+        ));
+    } else {
+        commands.spawn((
+            FlashEffect,
+            Lifetime::new(0.07),
+            Mesh2d(rect_mesh.clone()),
+            MeshMaterial2d(rect_color.clone()),
+            Transform::from_xyz(0.0, 0.0, Z_POS_GHOSTS + 3.0),
+        ));
+    }
     let mut rng = rand::rng();
     let soul_sprite = sprites.ghost_soul.as_ref().expect("Images should be loaded");
     let star_sprite = sprites.ghost_particles.as_ref().expect("Images should be loaded");
     for captured in on_capture.read() {
-        if let Ok((ghost_root, ghost_children)) = ghost_roots.get(captured.entity) {
+        if let Ok((ghost_root, ghost_children, ghost_pos)) = ghost_roots.get(captured.entity) {
+            on_sfx.write(PlaySfx {
+                sound: SfxSound::Capture,
+                position: Some(ghost_pos.translation().xy()),
+            });
             for &child in ghost_children {
                 if let Ok(ghost_sprite_pos) = ghost_sprites.get(child) {
                     commands.spawn((
@@ -1546,6 +4188,18 @@ fn handle_ghosts_captured(
     }
 }
 
+fn fade_flash_effects(
+    flashes: Query<(&Lifetime, &MeshMaterial2d<ColorMaterial>), With<FlashFade>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    for (lifetime, material) in flashes {
+        let Some(material) = materials.get_mut(&material.0) else { continue };
+        let progress = 1.0 - lifetime.lifetime / lifetime.total_lifetime;
+        let alpha = (progress * std::f32::consts::PI).sin() * REDUCED_MOTION_FADE_ALPHA;
+        material.color = material.color.with_alpha(alpha);
+    }
+}
+
 fn update_lifetimes(
     time: Res<Time>,
     lifetimes: Query<(Entity, &mut Lifetime)>,
@@ -1674,6 +4328,302 @@ fn update_remote_invert_switches(
     }
 }
 
+fn spawn_state_indicators(
+    lights: Query<(Entity, &WaveButtonLight), Added<WaveButtonLight>>,
+    inverters: Query<(Entity, &InverterSwitch), Added<InverterSwitch>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut commands: Commands,
+) {
+    if lights.is_empty() && inverters.is_empty() {
+        return;
+    }
+    let backing_mesh = meshes.add(Circle::new(48.0));
+    let backing_color = materials.add(Color::BLACK);
+    let shape_color = materials.add(Color::WHITE);
+    // filled for on, hollow for off
+    let on_mesh = meshes.add(Circle::new(34.0));
+    let off_mesh = meshes.add(Annulus::new(24.0, 36.0));
+    // pointing up for normal, down for inverted
+    let normal_mesh = meshes.add(Triangle2d::new(
+        Vec2::new(0.0, 34.0),
+        Vec2::new(-32.0, -24.0),
+        Vec2::new(32.0, -24.0)));
+    let inverted_mesh = meshes.add(Triangle2d::new(
+        Vec2::new(0.0, -34.0),
+        Vec2::new(32.0, 24.0),
+        Vec2::new(-32.0, 24.0)));
+    let switches = lights.iter()
+        .map(|(entity, light)| (entity, light.button_idx, RemoteSwitchKind::Light))
+        .chain(inverters.iter().map(|(entity, inverter)| (entity, inverter.button_idx, RemoteSwitchKind::Inverter)));
+    for (entity, button_idx, kind) in switches {
+        let (on_shape, off_shape, label_y) = match kind {
+            RemoteSwitchKind::Light => (&on_mesh, &off_mesh, -75.0),
+            RemoteSwitchKind::Inverter => (&inverted_mesh, &normal_mesh, -115.0),
+        };
+        commands.entity(entity).with_children(|cmd| {
+            for (shape, shown_when) in [(on_shape, true), (off_shape, false)] {
+                cmd.spawn((
+                    StateIndicator {
+                        button_idx,
+                        kind,
+                        shown_when,
+                    },
+                    Mesh2d(backing_mesh.clone()),
+                    MeshMaterial2d(backing_color.clone()),
+                    Transform::from_xyz(0.0, 0.0, 1.0),
+                    Visibility::Hidden,
+                )).with_child((
+                    Mesh2d(shape.clone()),
+                    MeshMaterial2d(shape_color.clone()),
+                    Transform::from_xyz(0.0, 0.0, 0.1),
+                ));
+            }
+            cmd.spawn((
+                StateLabel {
+                    button_idx,
+                    kind,
+                },
+                Text2d::default(),
+                TextFont::from_font_size(45.0),
+                Transform::from_xyz(0.0, label_y, 1.0),
+                Visibility::Hidden,
+            ));
+        });
+    }
+}
+
+fn update_state_indicators(
+    save_data: Res<SaveData>,
+    ghost_wave: Res<GhostWaveConfig>,
+    strings: Res<Strings>,
+    indicators: Query<(&mut Visibility, &StateIndicator), Without<StateLabel>>,
+    labels: Query<(&mut Visibility, &mut Text2d, &StateLabel), Without<StateIndicator>>,
+) {
+    let settings = save_data.accessibility;
+    let switch_state = |button_idx: i8, kind: RemoteSwitchKind| {
+        let button = &ghost_wave.buttons[button_idx as usize];
+        return match kind {
+            RemoteSwitchKind::Light => button.enabled,
+            RemoteSwitchKind::Inverter => button.inverted,
+        };
+    };
+    for (mut visibility, indicator) in indicators {
+        let shown = settings.high_contrast
+            && switch_state(indicator.button_idx, indicator.kind) == indicator.shown_when;
+        visibility.set_if_neq(if shown { Visibility::Inherited } else { Visibility::Hidden });
+    }
+    for (mut visibility, mut text, label) in labels {
+        visibility.set_if_neq(if settings.state_labels { Visibility::Inherited } else { Visibility::Hidden });
+        let label_text = strings.get(match (label.kind, switch_state(label.button_idx, label.kind)) {
+            (RemoteSwitchKind::Light, true) => "switch.on",
+            (RemoteSwitchKind::Light, false) => "switch.off",
+            (RemoteSwitchKind::Inverter, true) => "switch.inverted",
+            (RemoteSwitchKind::Inverter, false) => "switch.normal",
+        });
+        if text.0 != label_text {
+            text.0 = label_text.to_string();
+        }
+    }
+}
+
+fn update_ghost_palette(
+    save_data: Res<SaveData>,
+    catalog: Res<GhostCatalog>,
+    ghost_sprites: Query<(Ref<GhostTint>, &mut Sprite), Without<BackgroundImage>>,
+    backgrounds: Query<&mut Sprite, With<BackgroundImage>>,
+) {
+    let high_contrast = save_data.accessibility.high_contrast;
+    for (tint, mut sprite) in ghost_sprites {
+        if !save_data.is_changed() && !tint.is_added() {
+            continue;
+        }
+        // keep whatever fading the sprite already has, like the twin's echo
+        let alpha = sprite.color.alpha();
+        sprite.color = catalog.tint(tint.0, high_contrast).with_alpha(alpha);
+    }
+    if save_data.is_changed() {
+        for mut sprite in backgrounds {
+            sprite.color = if high_contrast { Color::srgb(0.35, 0.35, 0.4) } else { Color::WHITE };
+        }
+    }
+}
+
+fn toggle_accessibility(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut save_data: ResMut<SaveData>,
+) {
+    if keys.just_pressed(KeyCode::KeyH) {
+        save_data.accessibility.high_contrast = !save_data.accessibility.high_contrast;
+    } else if keys.just_pressed(KeyCode::KeyT) {
+        save_data.accessibility.state_labels = !save_data.accessibility.state_labels;
+    } else if keys.just_pressed(KeyCode::KeyM) {
+        save_data.accessibility.reduced_motion = !save_data.accessibility.reduced_motion;
+    } else {
+        return;
+    }
+    save_data.save();
+}
+
+// A line for screen readers to announce. Everything written in a frame gets read out together
+#[derive(Message)]
+struct Narrate(String);
+
+// A live region that screen readers announce whenever its label changes
+#[derive(Component)]
+struct Narrator;
+
+fn spawn_narrator(
+    mut commands: Commands,
+) {
+    let mut node = AccessNode::new(Role::Status);
+    node.set_live(Live::Polite);
+    commands.spawn((
+        Narrator,
+        AccessibilityNode(node),
+    ));
+    // the page's live region has to be there before anything is said into it
+    #[cfg(target_arch = "wasm32")]
+    announce_on_page("");
+}
+
+fn update_narrator(
+    mut on_narrate: MessageReader<Narrate>,
+    narrators: Query<&mut AccessibilityNode, With<Narrator>>,
+    mut last_narration: Local<String>,
+    mut repeat: Local<Option<String>>,
+) {
+    if on_narrate.is_empty() {
+        if let Some(narration) = repeat.take() {
+            show_narration(narrators, &narration);
+        }
+        return;
+    }
+    let lines: Vec<&str> = on_narrate.read().map(|narrate| narrate.0.as_str()).collect();
+    let narration = lines.join(". ");
+    *repeat = None;
+    if narration == *last_narration {
+        // setting the same label again doesn't get it read out, so blank it for a frame first
+        show_narration(narrators, "");
+        *repeat = Some(narration);
+        return;
+    }
+    show_narration(narrators, &narration);
+    *last_narration = narration;
+}
+
+fn show_narration(narrators: Query<&mut AccessibilityNode, With<Narrator>>, narration: &str) {
+    for mut node in narrators {
+        if narration.is_empty() {
+            node.clear_label();
+        } else {
+            node.set_label(narration);
+        }
+    }
+    // accesskit has nothing to talk to in the browser, so the page gets its own live region
+    #[cfg(target_arch = "wasm32")]
+    announce_on_page(narration);
+}
+
+#[cfg(target_arch = "wasm32")]
+fn announce_on_page(narration: &str) {
+    let Some(document) = web_sys::window().and_then(|window| window.document()) else { return };
+    let region = match document.get_element_by_id(NARRATION_ELEMENT_ID) {
+        Some(region) => region,
+        None => {
+            let Ok(region) = document.create_element("div") else { return };
+            region.set_id(NARRATION_ELEMENT_ID);
+            let _ = region.set_attribute("role", "status");
+            let _ = region.set_attribute("aria-live", "polite");
+            // there for screen readers, but not on screen
+            let _ = region.set_attribute(
+                "style",
+                "position: absolute; width: 1px; height: 1px; overflow: hidden; clip: rect(0 0 0 0);");
+            let Some(body) = document.body() else { return };
+            if body.append_child(&region).is_err() {
+                return;
+            }
+            region
+        }
+    };
+    region.set_text_content(Some(narration));
+}
+
+fn describe_remote(ghost_wave: &GhostWaveConfig, strings: &Strings) -> String {
+    let enabled: Vec<String> = ghost_wave.buttons.iter()
+        .enumerate()
+        .filter(|(_, button)| button.enabled)
+        .map(|(idx, button)| {
+            let wave = strings.get(&format!("wave.{}", GHOST_WAVE_NAMES[idx])).to_string();
+            return if button.inverted { strings.format("narration.inverted", &[&wave]) } else { wave };
+        })
+        .collect();
+    let buttons = if enabled.is_empty() {
+        strings.get("narration.no_buttons").to_string()
+    } else {
+        strings.format("narration.buttons", &[&enabled.join(", ")])
+    };
+    return strings.format("narration.dial", &[&buttons, &ghost_wave.dial_strength]);
+}
+
+fn describe_resources(resources: &PlayerResources, settings: &GameSettings, strings: &Strings) -> String {
+    if settings.mode.keeps_score() {
+        return strings.format(
+            "narration.resources_score",
+            &[&resources.charges, &resources.reputation, &resources.score]);
+    }
+    return strings.format("narration.resources", &[&resources.charges, &resources.reputation]);
+}
+
+fn narrate_round_start(
+    catalog: Res<GhostCatalog>,
+    strings: Res<Strings>,
+    target_ghosts: Res<TargetGhostTags>,
+    ghost_wave: Res<GhostWaveConfig>,
+    resources: Res<PlayerResources>,
+    settings: Res<GameSettings>,
+    mut on_narrate: MessageWriter<Narrate>,
+) {
+    let targets: Vec<String> = target_ghosts.targets.iter()
+        .map(|target| catalog.display_name(target, &strings))
+        .collect();
+    on_narrate.write(Narrate(strings.format(
+        "narration.round_start",
+        &[&strings.get(settings.mode.label_key()), &targets.join(strings.get("narration.and"))])));
+    on_narrate.write(Narrate(describe_remote(&ghost_wave, &strings)));
+    on_narrate.write(Narrate(describe_resources(&resources, &settings, &strings)));
+}
+
+fn narrate_remote_changes(
+    strings: Res<Strings>,
+    ghost_wave: Res<GhostWaveConfig>,
+    resources: Res<PlayerResources>,
+    settings: Res<GameSettings>,
+    mut on_narrate: MessageWriter<Narrate>,
+) {
+    if ghost_wave.is_changed() && !ghost_wave.is_added() {
+        on_narrate.write(Narrate(describe_remote(&ghost_wave, &strings)));
+    }
+    if resources.is_changed() && !resources.is_added() {
+        on_narrate.write(Narrate(describe_resources(&resources, &settings, &strings)));
+    }
+}
+
+fn narrate_round_end(
+    strings: Res<Strings>,
+    mut on_win: MessageReader<GameWon>,
+    mut on_lose: MessageReader<GameLost>,
+    mut on_narrate: MessageWriter<Narrate>,
+) {
+    if !on_win.is_empty() {
+        on_narrate.write(Narrate(strings.get("narration.won").to_string()));
+    } else if !on_lose.is_empty() {
+        on_narrate.write(Narrate(strings.get("narration.lost").to_string()));
+    }
+    on_win.clear();
+    on_lose.clear();
+}
+
 fn update_remote_dial(
     sprites: Res<Sprites>,
     ghost_wave: Res<GhostWaveConfig>,
@@ -1702,23 +4652,95 @@ fn update_wave_handle(
 
 fn update_counters(
     sprites: Res<Sprites>,
+    settings: Res<GameSettings>,
     player_resources: Res<PlayerResources>,
-    counters: Query<(&mut Sprite, &ResourceCounter)>
+    versus: Res<VersusTurn>,
+    timer: Res<RoundTimer>,
+    save_data: Res<SaveData>,
+    time: Res<Time>,
+    counters: Query<(Entity, &mut Visibility, &mut CounterDisplay, &ResourceCounter, &CounterOwner, Option<&Children>)>,
+    digits: Query<&Children, With<CounterDigit>>,
+    mut digit_texts: Query<(&mut Text2d, &mut TextColor, &mut Transform), With<CounterDigitText>>,
+    mut commands: Commands,
 ) {
-    let counter_sprites = sprites.frame_counter.as_ref().expect("Sprites should be loaded");
-    for (mut sprite, counter_type) in counters {
-        let sprite_idx = match *counter_type {
-            ResourceCounter::Reputation => player_resources.reputation,
-            ResourceCounter::Charges => player_resources.charges,
-        } as usize;
-        sprite.image = counter_sprites[sprite_idx].clone();
+    let reduced_motion = save_data.accessibility.reduced_motion;
+    for (counter, mut visibility, mut display, counter_type, owner, children) in counters {
+        let shown_in_mode = match *counter_type {
+            ResourceCounter::Score => settings.mode.keeps_score(),
+            ResourceCounter::RoundTimer => settings.mode == GameMode::Timed,
+            _ => owner.player == 0 || settings.mode == GameMode::Versus,
+        };
+        if !shown_in_mode {
+            *visibility = Visibility::Hidden;
+            continue;
+        }
+        *visibility = Visibility::Inherited;
+        let player_resources = if owner.player == versus.current {
+            &*player_resources
+        } else {
+            &versus.waiting
+        };
+        let value = match *counter_type {
+            ResourceCounter::Reputation => player_resources.reputation as i64,
+            ResourceCounter::Charges => player_resources.charges as i64,
+            ResourceCounter::Score => player_resources.score as i64,
+            ResourceCounter::RoundTimer => timer.remaining.ceil() as i64,
+        };
+        display.tick_timer = (display.tick_timer - time.delta_secs()).max(0.0);
+        // the clock gets wound straight back up for a new round rather than ticking all the way
+        let winding_clock = *counter_type == ResourceCounter::RoundTimer && value > display.shown;
+        if display.shown != value && (reduced_motion || winding_clock) {
+            display.shown = value;
+        } else if display.shown != value && display.tick_timer <= 0.0 {
+            display.shown += (value - display.shown).signum();
+            display.tick_timer = COUNTER_TICK_SECONDS;
+        }
+
+        // always at least two digits, like a flip counter
+        let text = if *counter_type == ResourceCounter::RoundTimer {
+            format!("{}:{:02}", display.shown / 60, display.shown % 60)
+        } else {
+            format!("{:02}", display.shown)
+        };
+        let digit_color = if *counter_type == ResourceCounter::RoundTimer
+            && display.shown as f32 <= TIMED_MODE_WARNING_SECONDS
+        {
+            Color::srgb(1.0, 0.3, 0.25)
+        } else {
+            COUNTER_DIGIT_COLOR
+        };
+        let counter_digits: Vec<Entity> = children.iter()
+            .flat_map(|children| children.iter())
+            .filter(|child| digits.contains(*child))
+            .collect();
+        if counter_digits.len() != text.chars().count() {
+            for digit in counter_digits {
+                commands.entity(digit).despawn();
+            }
+            spawn_counter_digits(&mut commands, counter, &sprites, &text);
+            continue;
+        }
+        let squash = 1.0 - 0.5 * display.tick_timer / COUNTER_TICK_SECONDS;
+        for (digit, digit_char) in counter_digits.iter().zip(text.chars()) {
+            let Ok(digit_children) = digits.get(*digit) else { continue };
+            for &child in digit_children {
+                let Ok((mut digit_text, mut text_color, mut transform)) = digit_texts.get_mut(child) else { continue };
+                let digit_string = digit_char.to_string();
+                if digit_text.0 != digit_string {
+                    digit_text.0 = digit_string;
+                }
+                text_color.0 = digit_color;
+                transform.scale.y = squash;
+            }
+        }
     }
 }
 
 fn handle_ui_enabled(
     mut ui_enabled: ResMut<UIEnabled>,
     ghosts: Query<&GhostScooting, With<Ghost>>,
-    sfx: Query<Entity, With<Sfx>>,
+    // the wave's hum stops with the ghosts, anything else gets to finish
+    sfx: Query<Entity, (With<Sfx>, Without<SfxVoice>)>,
     game_ends: Query<Entity, With<GameEndSplash>>,
     mut commands: Commands,
 ) {
@@ -1780,23 +4802,499 @@ fn handle_game_end(
     on_lose.clear();
 }
 
+fn reset_round_timer(
+    mut timer: ResMut<RoundTimer>,
+) {
+    timer.remaining = TIMED_MODE_SECONDS;
+}
+
+fn update_round_timer(
+    time: Res<Time>,
+    settings: Res<GameSettings>,
+    ui_enabled: Res<UIEnabled>,
+    mut timer: ResMut<RoundTimer>,
+    mut resources: ResMut<PlayerResources>,
+    mut on_win: MessageReader<GameWon>,
+    mut on_lose: MessageReader<GameLost>,
+    mut commands: Commands,
+) {
+    if settings.mode != GameMode::Timed {
+        on_win.clear();
+        on_lose.clear();
+        return;
+    }
+    if !on_win.is_empty() {
+        // bank whatever is left on the clock, then stop it so it can't run out mid-splash
+        on_win.clear();
+        on_lose.clear();
+        resources.score += timer.remaining.ceil() as u32;
+        timer.remaining = 0.0;
+        return;
+    }
+    if !on_lose.is_empty() {
+        // already lost some other way, so the clock running out can't end it a second time
+        on_lose.clear();
+        timer.remaining = 0.0;
+        return;
+    }
+    // the clock only runs while the player can act
+    if ui_enabled.moving_ghosts || timer.remaining <= 0.0 {
+        return;
+    }
+    timer.remaining -= time.delta_secs();
+    if timer.remaining <= 0.0 {
+        timer.remaining = 0.0;
+        commands.write_message(GameLost);
+    }
+}
+
+fn record_endless_score(
+    settings: Res<GameSettings>,
+    resources: Res<PlayerResources>,
+    mut save_data: ResMut<SaveData>,
+    game_end_splash: Query<Entity, With<GameEndSplash>>,
+    mut commands: Commands,
+) {
+    if settings.mode != GameMode::Endless {
+        return;
+    }
+    let rank = save_data.record_endless_score(resources.score);
+    save_data.save();
+
+    let mut table = String::new();
+    for (i, high_score) in save_data.endless_high_scores.iter().enumerate() {
+        let marker = if Some(i) == rank { "  <" } else { "" };
+        table.push_str(&format!("\n{}. {}{}", i + 1, high_score, marker));
+    }
+    // the heading hangs above the table and the scores below it, so either can change length
+    for splash in game_end_splash {
+        commands.entity(splash).with_child((
+            Text2d::default(),
+            LocalizedText::with_args("endless.high_scores", vec![resources.score.to_string()]),
+            TextFont::from_font_size(70.0),
+            Anchor::BOTTOM_CENTER,
+            Transform::from_xyz(0.0, -400.0, 1.0),
+        ));
+        commands.entity(splash).with_child((
+            Text2d::new(table.clone()),
+            TextFont::from_font_size(70.0),
+            Anchor::TOP_CENTER,
+            Transform::from_xyz(0.0, -400.0, 1.0),
+        ));
+    }
+}
+
+fn reset_versus_turn(
+    settings: Res<GameSettings>,
+    mut resources: ResMut<PlayerResources>,
+    mut versus: ResMut<VersusTurn>,
+) {
+    if settings.mode != GameMode::Versus {
+        return;
+    }
+    // every round is a fresh match
+    *resources = PlayerResources { charges: 10, reputation: 5, score: 0 };
+    versus.waiting = PlayerResources { charges: 10, reputation: 5, score: 0 };
+    versus.current = 0;
+    versus.last_charges = resources.charges;
+}
+
+// Hands the remote over after every firing that spends a charge or any capture, unless that's
+// what ended the round
+fn advance_versus_turn(
+    settings: Res<GameSettings>,
+    mut resources: ResMut<PlayerResources>,
+    mut versus: ResMut<VersusTurn>,
+    mut on_fire: MessageReader<RemoteFired>,
+    mut on_capture_fired: MessageReader<CaptureGhostsInitialized>,
+    mut on_win: MessageReader<GameWon>,
+    mut on_lose: MessageReader<GameLost>,
+) {
+    // firing with nothing that moves doesn't cost anything, so it doesn't use up the turn either
+    let charge_spent = !on_fire.is_empty() && resources.charges != versus.last_charges;
+    let turn_taken = charge_spent || !on_capture_fired.is_empty();
+    let round_over = !on_win.is_empty() || !on_lose.is_empty();
+    on_fire.clear();
+    on_capture_fired.clear();
+    on_win.clear();
+    on_lose.clear();
+    if settings.mode != GameMode::Versus {
+        return;
+    }
+    if turn_taken && !round_over {
+        std::mem::swap(&mut *resources, &mut versus.waiting);
+        versus.current = 1 - versus.current;
+    }
+    versus.last_charges = resources.charges;
+}
+
+fn update_versus_turn_labels(
+    settings: Res<GameSettings>,
+    versus: Res<VersusTurn>,
+    labels: Query<(&VersusTurnLabel, &mut TextColor, &mut Visibility)>,
+) {
+    for (label, mut color, mut visibility) in labels {
+        *visibility = if settings.mode == GameMode::Versus {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+        color.0 = if label.player == versus.current {
+            Color::srgb(1.0, 0.8, 0.2)
+        } else {
+            Color::srgba(1.0, 1.0, 1.0, 0.3)
+        };
+    }
+}
+
+fn announce_versus_winner(
+    settings: Res<GameSettings>,
+    resources: Res<PlayerResources>,
+    versus: Res<VersusTurn>,
+    game_end_splash: Query<(Entity, &GameEndSplash)>,
+    mut commands: Commands,
+) {
+    if settings.mode != GameMode::Versus {
+        return;
+    }
+    let Ok((splash, game_end)) = game_end_splash.single() else { return };
+    let other = 1 - versus.current;
+    // the turn never passes on the move that ended the round, so a loss is always the current
+    // player's fault. Otherwise the best reputation wins, with ties going to whoever finished it
+    let winner = match *game_end {
+        GameEndSplash::Lose => other,
+        GameEndSplash::Win if versus.waiting.reputation > resources.reputation => other,
+        GameEndSplash::Win => versus.current,
+    };
+    commands.entity(splash).with_child((
+        Text2d::default(),
+        LocalizedText::with_args("versus.winner", vec![(winner + 1).to_string()]),
+        TextFont::from_font_size(120.0),
+        Transform::from_xyz(0.0, -500.0, 1.0),
+    ));
+}
+
+#[derive(Component)]
+struct ModeMenu;
+
+fn spawn_mode_menu(
+    save_data: Res<SaveData>,
+    mut commands: Commands,
+) {
+    let todays_result = save_data.daily_result.as_ref()
+        .filter(|result| result.day == current_day());
+    const MENU_ENTRY_SPACING_Y: f32 = 320.0;
+    let entries_top = MENU_ENTRY_SPACING_Y * (GameMode::ALL.len() - 1) as f32 / 2.0;
+    commands.spawn((
+        ModeMenu,
+        Sprite::from_color(Color::srgba(0.0, 0.0, 0.0, 0.75), Vec2::new(GAME_AREA_WIDTH, GAME_AREA_HEIGHT)),
+        Transform::from_xyz(0.0, 0.0, Z_POS_MENU),
+    )).with_children(|cmd| {
+        cmd.spawn((
+            Text2d::new("GRAVEYARD SHIFTS"),
+            TextFont::from_font_size(160.0),
+            Transform::from_xyz(0.0, entries_top + 420.0, 1.0),
+        ));
+        for (i, mode) in GameMode::ALL.iter().enumerate() {
+            cmd.spawn((
+                Transform::from_xyz(0.0, entries_top - MENU_ENTRY_SPACING_Y * i as f32, 1.0),
+                Visibility::Inherited,
+                Clickable {
+                    clickable_type: ClickableType::SelectMode(*mode),
+                    bounds: Rect::new(-900.0, -130.0, 900.0, 130.0),
+                },
+            )).with_children(|entry| {
+                entry.spawn((
+                    Text2d::default(),
+                    LocalizedText::new(mode.label_key()),
+                    TextFont::from_font_size(110.0),
+                    Transform::from_xyz(0.0, 40.0, 0.0),
+                ));
+                let description = match (mode, todays_result) {
+                    (GameMode::Daily, Some(result)) if result.won => {
+                        LocalizedText::with_args("daily.done_won", vec![result.charges_used.to_string()])
+                    },
+                    (GameMode::Daily, Some(_)) => LocalizedText::new("daily.done_lost"),
+                    _ => LocalizedText::new(mode.description_key()),
+                };
+                entry.spawn((
+                    Text2d::default(),
+                    description,
+                    TextFont::from_font_size(55.0),
+                    TextColor(Color::srgb(0.7, 0.7, 0.8)),
+                    Transform::from_xyz(0.0, -70.0, 0.0),
+                ));
+            });
+        }
+        cmd.spawn((
+            Text2d::default(),
+            LocalizedText::new("menu.accessibility_hint"),
+            TextFont::from_font_size(45.0),
+            TextColor(Color::srgba(1.0, 1.0, 1.0, 0.6)),
+            Transform::from_xyz(0.0, -1060.0, 1.0),
+        ));
+        cmd.spawn((
+            Text2d::default(),
+            LocalizedText::new("menu.language_hint"),
+            TextFont::from_font_size(45.0),
+            TextColor(Color::srgba(1.0, 1.0, 1.0, 0.6)),
+            Transform::from_xyz(0.0, -1130.0, 1.0),
+        ));
+    });
+}
+
+fn handle_menu_clicks(
+    mouse_button: Res<ButtonInput<MouseButton>>,
+    window: Query<&Window, With<PrimaryWindow>>,
+    camera: Query<(&Camera, &GlobalTransform)>,
+    query: Query<(&GlobalTransform, &Clickable)>,
+    catalog: Res<GhostCatalog>,
+    mut capture_zones: ResMut<CaptureZones>,
+    mut target_ghosts: ResMut<TargetGhostTags>,
+    mut ghost_wave: ResMut<GhostWaveConfig>,
+    mut lanes: ResMut<LaneLayout>,
+    mut resources: ResMut<PlayerResources>,
+    mut daily: ResMut<DailyChallenge>,
+    mut save_data: ResMut<SaveData>,
+    mut settings: ResMut<GameSettings>,
+    mut state: ResMut<NextState<GameState>>,
+    mut on_sfx: MessageWriter<PlaySfx>,
+    strings: Res<Strings>,
+) {
+    if !mouse_button.just_pressed(MouseButton::Left) {
+        return;
+    }
+    let Some(cursor_pos) = get_cursor_world_pos(&window, &camera) else { return };
+    for (clickable_transform, clickable) in query {
+        if !clickable.contains(clickable_transform, cursor_pos) {
+            continue;
+        }
+        let ClickableType::SelectMode(mode) = clickable.clickable_type else { continue };
+        let day = current_day();
+        if mode == GameMode::Daily && save_data.daily_result.as_ref().is_some_and(|result| result.day == day) {
+            continue;
+        }
+        // every mode starts from scratch, nothing carries over from whatever was played last
+        *resources = PlayerResources { charges: 10, reputation: 5, score: 0 };
+        if mode == GameMode::Daily {
+            roll_round(
+                &catalog,
+                &mut capture_zones,
+                &mut target_ghosts,
+                &mut ghost_wave,
+                &mut lanes,
+                &mut daily_rng(day, DAILY_ROUND_SALT));
+            *daily = DailyChallenge {
+                day,
+                last_charges: resources.charges,
+                ..default()
+            };
+            // the attempt counts as soon as it starts, quitting out early is a loss
+            save_data.daily_result = Some(DailyResult {
+                day,
+                won: false,
+                charges_used: 0,
+                summary: daily.summary(false, 0, &strings),
+            });
+            save_data.save();
+        }
+        settings.mode = mode;
+        state.set(GameState::Game);
+        on_sfx.write(PlaySfx { sound: SfxSound::Click, position: None });
+    }
+}
+
+// Sum of how many lanes each target still has to travel to get to its capture zone
+fn get_target_distance(
+    target: &TargetGhostTags,
+    capture_zones: &CaptureZones,
+    ghosts: &Query<(&GhostLanePosition, &GhostTags), With<Ghost>>,
+) -> u32 {
+    return ghosts.iter()
+        .filter_map(|(ghost_lane, ghost_tags)| {
+            let target_idx = target.targets.iter().position(|target| target == ghost_tags)?;
+            return capture_zones.zones.iter()
+                .filter(|zone| zone.targets.contains(&target_idx))
+                .map(|zone| ghost_lane.lane.abs_diff(zone.lane) as u32)
+                .min();
+        })
+        .sum();
+}
+
+fn track_daily_progress(
+    settings: Res<GameSettings>,
+    target: Res<TargetGhostTags>,
+    capture_zones: Res<CaptureZones>,
+    resources: Res<PlayerResources>,
+    mut daily: ResMut<DailyChallenge>,
+    mut on_fire: MessageReader<RemoteFired>,
+    mut on_capture_fired: MessageReader<CaptureGhostsInitialized>,
+    ghosts: Query<(&GhostLanePosition, &GhostTags), With<Ghost>>,
+) {
+    if settings.mode != GameMode::Daily {
+        on_fire.clear();
+        on_capture_fired.clear();
+        return;
+    }
+    let target_distance = get_target_distance(&target, &capture_zones, &ghosts);
+    // only firings that actually cost a charge make it into the summary
+    if !on_fire.is_empty() && resources.charges != daily.last_charges {
+        let cell = if target_distance < daily.last_target_distance {
+            "🟩"
+        } else if target_distance > daily.last_target_distance {
+            "🟥"
+        } else {
+            "⬜"
+        };
+        daily.cells.push(cell);
+    }
+    on_fire.clear();
+    for _ in on_capture_fired.read() {
+        daily.cells.push("👻");
+    }
+    daily.last_target_distance = target_distance;
+    daily.last_charges = resources.charges;
+}
+
+fn record_daily_result(
+    settings: Res<GameSettings>,
+    resources: Res<PlayerResources>,
+    daily: Res<DailyChallenge>,
+    strings: Res<Strings>,
+    mut save_data: ResMut<SaveData>,
+    game_end_splash: Query<(Entity, &GameEndSplash)>,
+    mut commands: Commands,
+) {
+    if settings.mode != GameMode::Daily {
+        return;
+    }
+    let Ok((splash, game_end)) = game_end_splash.single() else { return };
+    let won = *game_end == GameEndSplash::Win;
+    let charges_used = 10 - resources.charges;
+    let summary = daily.summary(won, charges_used, &strings);
+    info!("Daily summary:\n{summary}");
+
+    // the result and its squares so it can be read off the screen, the font has no emoji
+    commands.entity(splash).with_child((
+        Text2d::default(),
+        if won {
+            LocalizedText::with_args("daily.done_won", vec![charges_used.to_string()])
+        } else {
+            LocalizedText::new("daily.done_lost")
+        },
+        TextFont::from_font_size(60.0),
+        Transform::from_xyz(0.0, -420.0, 1.0),
+    ));
+    let mut cells_bottom = -420.0;
+    for (row_idx, row) in daily.cells.chunks(DAILY_SUMMARY_ROW_LENGTH).enumerate() {
+        let y = -520.0 - row_idx as f32 * DAILY_SUMMARY_CELL_SPACING;
+        for (col_idx, cell) in row.iter().enumerate() {
+            let x = (col_idx as f32 - (row.len() - 1) as f32 / 2.0) * DAILY_SUMMARY_CELL_SPACING;
+            commands.entity(splash).with_child((
+                Sprite::from_color(daily_cell_color(cell), Vec2::splat(DAILY_SUMMARY_CELL_SIZE)),
+                Transform::from_xyz(x, y, 1.0),
+            ));
+        }
+        cells_bottom = y;
+    }
+    // there's no file to point anyone at on the web, so the summary gets copied from there
+    #[cfg(not(target_arch = "wasm32"))]
+    match write_stored_text(DAILY_SUMMARY_PATH, &summary) {
+        Ok(()) => {
+            commands.entity(splash).with_child((
+                Text2d::default(),
+                LocalizedText::with_args("daily.saved", vec![DAILY_SUMMARY_PATH.to_string()]),
+                TextFont::from_font_size(40.0),
+                Transform::from_xyz(0.0, cells_bottom - 90.0, 1.0),
+            ));
+        },
+        Err(err) => warn!("Couldn't write the daily summary: {err}"),
+    }
+    #[cfg(target_arch = "wasm32")]
+    commands.entity(splash).with_child((
+        DailyCopyHint,
+        Text2d::default(),
+        LocalizedText::new("daily.copy_hint"),
+        TextFont::from_font_size(40.0),
+        Transform::from_xyz(0.0, cells_bottom - 90.0, 1.0),
+    ));
+
+    save_data.daily_result = Some(DailyResult {
+        day: daily.day,
+        won,
+        charges_used,
+        summary,
+    });
+    save_data.save();
+}
+
+#[cfg(target_arch = "wasm32")]
+#[derive(Component)]
+struct DailyCopyHint;
+
+// C copies today's summary for pasting into chat, for as long as the result is on screen
+#[cfg(target_arch = "wasm32")]
+fn copy_daily_summary(
+    keys: Res<ButtonInput<KeyCode>>,
+    save_data: Res<SaveData>,
+    hints: Query<&mut LocalizedText, With<DailyCopyHint>>,
+) {
+    if !keys.just_pressed(KeyCode::KeyC) {
+        return;
+    }
+    let Some(result) = save_data.daily_result.as_ref() else { return };
+    let Some(window) = web_sys::window() else { return };
+    // the browser does the copying in the background, there's nothing to wait on
+    let _ = window.navigator().clipboard().write_text(&result.summary);
+    for mut hint in hints {
+        *hint = LocalizedText::new("daily.copied");
+    }
+}
+
+fn despawn_mode_menu(
+    menus: Query<Entity, With<ModeMenu>>,
+    mut commands: Commands,
+) {
+    for menu in menus {
+        commands.entity(menu).despawn();
+    }
+}
+
 #[derive(Component)]
 struct ResetTimer;
 
 fn spawn_reset_timer(
+    game_end_splash: Query<Entity, With<GameEndSplash>>,
     mut commands: Commands,
 ) {
     commands.spawn((
         ResetTimer,
         Lifetime::new(4.0),
     ));
+    for splash in game_end_splash {
+        commands.entity(splash).with_child((
+            Text2d::default(),
+            LocalizedText::new("game_end.menu_hint"),
+            TextFont::from_font_size(45.0),
+            TextColor(Color::srgba(1.0, 1.0, 1.0, 0.6)),
+            Transform::from_xyz(0.0, -1130.0, 1.0),
+        ));
+    }
 }
 
 fn wait_for_reset(
     query: Query<Entity, With<ResetTimer>>,
+    settings: Res<GameSettings>,
+    keys: Res<ButtonInput<KeyCode>>,
     mut state: ResMut<NextState<GameState>>,
 ) {
-    if query.is_empty() {
+    if keys.just_pressed(KeyCode::Escape) {
+        state.set(GameState::Menu);
+        return;
+    }
+    // the daily's summary stays up until the player is done reading or copying it, and there's
+    // only the one go at it so Escape heads back to pick something else
+    if query.is_empty() && settings.mode != GameMode::Daily {
         state.set(GameState::Game);
     }
 }